@@ -0,0 +1,51 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use json_peek::lexer::Lexer;
+use json_peek::parser::Parser;
+use json_peek_bench::datapack;
+use serde::de::IgnoredAny;
+
+const SIZES: [usize; 3] = [10, 1_000, 10_000];
+
+/// Token throughput of both lexers, serde_json doesn't expose its lexer so it validate the input without building anything instead
+fn lexer(c: &mut Criterion) {
+	let mut group = c.benchmark_group("lexer");
+
+	for size in SIZES.iter() {
+		let source = datapack(*size);
+		group.throughput(Throughput::Bytes(source.len() as u64));
+
+		group.bench_with_input(BenchmarkId::new("chars", size), &source, |b, source| {
+			b.iter(|| Lexer::new(source).into_iter().count())
+		});
+		group.bench_with_input(BenchmarkId::new("bytes", size), &source, |b, source| {
+			b.iter(|| Lexer::new(source).byte_iter().count())
+		});
+		group.bench_with_input(BenchmarkId::new("serde_json", size), &source, |b, source| {
+			b.iter(|| serde_json::from_str::<IgnoredAny>(source).unwrap())
+		});
+	}
+
+	group.finish();
+}
+
+/// Building the whole tree
+fn parser(c: &mut Criterion) {
+	let mut group = c.benchmark_group("parser");
+
+	for size in SIZES.iter() {
+		let source = datapack(*size);
+		group.throughput(Throughput::Bytes(source.len() as u64));
+
+		group.bench_with_input(BenchmarkId::new("json-peek", size), &source, |b, source| {
+			b.iter(|| Parser::new(source).parse().unwrap())
+		});
+		group.bench_with_input(BenchmarkId::new("serde_json", size), &source, |b, source| {
+			b.iter(|| serde_json::from_str::<serde_json::Value>(source).unwrap())
+		});
+	}
+
+	group.finish();
+}
+
+criterion_group!(benches, lexer, parser);
+criterion_main!(benches);
@@ -0,0 +1,31 @@
+//! Benchmarks for `json-peek`, run them with `cargo bench` inside of this directory.
+
+/// Datapack-like JSON with `entries` advancements, mixing translated text, escapes, numbers and nesting
+pub fn datapack(entries: usize) -> String {
+	let mut source = String::from("{\n\t\"values\": [\n");
+
+	for index in 0..entries {
+		if index > 0 {
+			source.push_str(",\n");
+		}
+		source.push_str(&format!(
+			r#"		{{
+			"display": {{
+				"title": {{ "text": "Advancement #{index}", "color": "gold", "bold": true }},
+				"description": {{ "translate": "advancements.story.魔法使い.{index}", "with": ["めぐみん", "\"Explosion\" \\ é"] }},
+				"icon": {{ "item": "minecraft:knowledge_book", "nbt": "{{CustomModelData: {index}}}" }},
+				"show_toast": false,
+				"announce_to_chat": null
+			}},
+			"criteria": {{
+				"tick": {{ "trigger": "minecraft:tick", "conditions": {{ "position": [{index}, -64.5, 0.125] }} }}
+			}},
+			"rewards": {{ "experience": {index}, "loot": ["minecraft:chests/end_city_treasure"] }}
+		}}"#,
+			index = index
+		));
+	}
+
+	source.push_str("\n\t]\n}\n");
+	source
+}
@@ -0,0 +1,19 @@
+#![no_main]
+use json_peek::lexer::Lexer;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+	if let Ok(source) = std::str::from_utf8(data) {
+		let lexer = Lexer::new(source);
+		let mut bytes = lexer.byte_iter();
+
+		for token in lexer {
+			let _ = token.value();
+
+			// Both lexers must agree on every token, including its span
+			let other = bytes.next().expect("Byte lexer ended early");
+			assert_eq!((token.span, token.kind), (other.span, other.kind));
+		}
+		assert!(bytes.next().is_none(), "Byte lexer has extra tokens");
+	}
+});
@@ -0,0 +1,16 @@
+#![no_main]
+use json_peek::parser::{Limits, Parser};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+	if let Ok(source) = std::str::from_utf8(data) {
+		let limits = Limits {
+			max_depth: 256,
+			..Limits::default()
+		};
+
+		if let Ok(value) = Parser::with_limits(source, limits).parse() {
+			value.validate_spans(source).expect("Invalid span");
+		}
+	}
+});
@@ -0,0 +1,121 @@
+use syn::{Attribute, Error, Lit, Meta, NestedMeta, Path, Result};
+
+/// What to do when a field is missing from the object
+pub enum Default {
+	Trait,
+	Function(Path),
+}
+
+/// `#[json(...)]` on a struct or an enum
+pub struct Container {
+	pub krate: Path,
+	pub deny_unknown_fields: bool,
+	pub tag: Option<String>,
+	pub untagged: bool,
+}
+
+/// `#[json(...)]` on a field
+#[derive(Default)]
+pub struct Field {
+	pub rename: Option<String>,
+	pub default: Option<Default>,
+	pub optional: bool,
+	pub flatten: bool,
+}
+
+/// `#[json(...)]` on an enum variant
+#[derive(Default)]
+pub struct Variant {
+	pub rename: Option<String>,
+}
+
+impl Container {
+	pub fn parse(attrs: &[Attribute]) -> Result<Container> {
+		let mut container = Container {
+			krate: syn::parse_quote!(::json_peek),
+			deny_unknown_fields: false,
+			tag: None,
+			untagged: false,
+		};
+
+		for meta in metas(attrs)? {
+			match &meta {
+				Meta::Path(path) if path.is_ident("deny_unknown_fields") => container.deny_unknown_fields = true,
+				Meta::Path(path) if path.is_ident("untagged") => container.untagged = true,
+				Meta::NameValue(pair) if pair.path.is_ident("tag") => container.tag = Some(string(&pair.lit)?),
+				Meta::NameValue(pair) if pair.path.is_ident("crate") => container.krate = syn::parse_str(&string(&pair.lit)?)?,
+				_ => return Err(Error::new_spanned(meta, "unknown container attribute")),
+			}
+		}
+
+		if container.untagged && container.tag.is_some() {
+			return Err(Error::new_spanned(&attrs[0], "enum can't be both tagged and untagged"));
+		}
+
+		Ok(container)
+	}
+}
+
+impl Field {
+	pub fn parse(attrs: &[Attribute]) -> Result<Field> {
+		let mut field = Field::default();
+
+		for meta in metas(attrs)? {
+			match &meta {
+				Meta::Path(path) if path.is_ident("default") => field.default = Some(Default::Trait),
+				Meta::Path(path) if path.is_ident("optional") => field.optional = true,
+				Meta::Path(path) if path.is_ident("flatten") => field.flatten = true,
+				Meta::NameValue(pair) if pair.path.is_ident("rename") => field.rename = Some(string(&pair.lit)?),
+				Meta::NameValue(pair) if pair.path.is_ident("default") => {
+					field.default = Some(Default::Function(syn::parse_str(&string(&pair.lit)?)?))
+				}
+				_ => return Err(Error::new_spanned(meta, "unknown field attribute")),
+			}
+		}
+
+		Ok(field)
+	}
+}
+
+impl Variant {
+	pub fn parse(attrs: &[Attribute]) -> Result<Variant> {
+		let mut variant = Variant::default();
+
+		for meta in metas(attrs)? {
+			match &meta {
+				Meta::NameValue(pair) if pair.path.is_ident("rename") => variant.rename = Some(string(&pair.lit)?),
+				_ => return Err(Error::new_spanned(meta, "unknown variant attribute")),
+			}
+		}
+
+		Ok(variant)
+	}
+}
+
+/// Every item inside of `#[json(...)]` attributes
+fn metas(attrs: &[Attribute]) -> Result<Vec<Meta>> {
+	let mut metas = Vec::new();
+
+	for attr in attrs.iter().filter(|x| x.path.is_ident("json")) {
+		match attr.parse_meta()? {
+			Meta::List(list) => {
+				for nested in list.nested {
+					match nested {
+						NestedMeta::Meta(meta) => metas.push(meta),
+						NestedMeta::Lit(lit) => return Err(Error::new_spanned(lit, "expected an attribute name")),
+					}
+				}
+			}
+			other => return Err(Error::new_spanned(other, "expected #[json(...)]")),
+		}
+	}
+
+	Ok(metas)
+}
+
+fn string(lit: &Lit) -> Result<String> {
+	match lit {
+		Lit::Str(lit) => Ok(lit.value()),
+		_ => Err(Error::new_spanned(lit, "expected a string")),
+	}
+}
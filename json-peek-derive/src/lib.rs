@@ -0,0 +1,226 @@
+#![deny(clippy::all)]
+
+//! `#[derive(FromValue)]` for [json-peek](https://docs.rs/json-peek), see `json_peek::value::FromValue` for the supported attributes.
+
+extern crate proc_macro;
+
+mod attr;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, FieldsNamed, Path, Result, Type};
+
+#[proc_macro_derive(FromValue, attributes(json))]
+pub fn derive_from_value(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	expand(&input).unwrap_or_else(|error| error.to_compile_error()).into()
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream> {
+	let container = attr::Container::parse(&input.attrs)?;
+	let krate = &container.krate;
+	let name = &input.ident;
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+	let (body, field_names) = match &input.data {
+		Data::Struct(data) => {
+			if container.tag.is_some() || container.untagged {
+				return Err(Error::new_spanned(input, "tag and untagged can only be used on enums"));
+			}
+			match &data.fields {
+				Fields::Named(fields) => {
+					let names = field_names(krate, fields, None)?;
+					(named_fields(&container, fields, quote!(Self), None)?, Some(names))
+				}
+				Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+					let ty = &fields.unnamed[0].ty;
+					(quote!(<#ty as #krate::value::FromValue>::from_value(value).map(Self)), None)
+				}
+				_ => return Err(Error::new_spanned(input, "only structs with named fields or a single unnamed field are supported")),
+			}
+		}
+		Data::Enum(data) => (enumeration(&container, name, data)?, None),
+		Data::Union(_) => return Err(Error::new_spanned(input, "unions are not supported")),
+	};
+
+	let field_names = field_names.map(|names| {
+		quote! {
+			fn field_names() -> ::std::vec::Vec<&'static str> {
+				#names
+			}
+		}
+	});
+
+	Ok(quote! {
+		impl #impl_generics #krate::value::FromValue for #name #ty_generics #where_clause {
+			fn from_value(value: &#krate::value::Value) -> ::std::result::Result<Self, ::std::vec::Vec<#krate::value::ConversionError>> {
+				#body
+			}
+
+			#field_names
+		}
+	})
+}
+
+/// Key of a field or a variant
+fn key(rename: &Option<String>, ident: &syn::Ident) -> String {
+	rename.clone().unwrap_or_else(|| ident.to_string().trim_start_matches("r#").to_owned())
+}
+
+fn is_option(ty: &Type) -> bool {
+	match ty {
+		Type::Path(path) => matches!(path.path.segments.last(), Some(segment) if segment.ident == "Option"),
+		_ => false,
+	}
+}
+
+/// Expression that evaluate to every key read by `fields`, `tag` is included if the fields belong to a tagged variant
+fn field_names(krate: &Path, fields: &FieldsNamed, tag: Option<&str>) -> Result<TokenStream> {
+	let mut keys = Vec::new();
+	let mut flatten = Vec::new();
+
+	for field in &fields.named {
+		let attr = attr::Field::parse(&field.attrs)?;
+		if attr.flatten {
+			flatten.push(&field.ty);
+		}
+		else {
+			keys.push(key(&attr.rename, field.ident.as_ref().unwrap()));
+		}
+	}
+	keys.extend(tag.map(str::to_owned));
+
+	Ok(quote! {{
+		#[allow(unused_mut)]
+		let mut names: ::std::vec::Vec<&'static str> = ::std::vec![#(#keys),*];
+		#(names.extend(<#flatten as #krate::value::FromValue>::field_names());)*
+		names
+	}})
+}
+
+/// Read every field from the object in `value` and build `constructor` out of them, every error is collected before returning
+fn named_fields(container: &attr::Container, fields: &FieldsNamed, constructor: TokenStream, tag: Option<&str>) -> Result<TokenStream> {
+	let krate = &container.krate;
+	let private = quote!(#krate::value::__private);
+	let mut reads = Vec::new();
+	let mut inits = Vec::new();
+
+	for (index, field) in fields.named.iter().enumerate() {
+		let attr = attr::Field::parse(&field.attrs)?;
+		let ident = field.ident.as_ref().unwrap();
+		let variable = format_ident!("field_{}", index);
+		let ty = &field.ty;
+
+		let read = if attr.flatten {
+			if attr.rename.is_some() || attr.default.is_some() || attr.optional {
+				return Err(Error::new_spanned(field, "flatten can't be used with other field attributes"));
+			}
+			quote!(#private::collect(value, <#ty as #krate::value::FromValue>::from_value(value), &mut errors))
+		}
+		else {
+			let key = key(&attr.rename, ident);
+			let missing = match &attr.default {
+				Some(attr::Default::Function(path)) => quote!(::std::option::Option::Some(#path as fn() -> #ty)),
+				Some(attr::Default::Trait) => quote!(::std::option::Option::Some(<#ty as ::std::default::Default>::default as fn() -> #ty)),
+				None if attr.optional || is_option(ty) => {
+					quote!(::std::option::Option::Some(<#ty as ::std::default::Default>::default as fn() -> #ty))
+				}
+				None => quote!(::std::option::Option::None),
+			};
+			quote!(#private::field::<#ty>(object, #key, #missing, &mut errors))
+		};
+
+		reads.push(quote!(let #variable = #read;));
+		inits.push(quote!(#ident: #variable.unwrap()));
+	}
+
+	let deny = if container.deny_unknown_fields {
+		let names = field_names(krate, fields, tag)?;
+		Some(quote!(#private::deny_unknown_fields(object, &#names, &mut errors);))
+	}
+	else {
+		None
+	};
+
+	Ok(quote! {
+		#[allow(unused_variables)]
+		let object = #private::object(value)?;
+		#[allow(unused_mut)]
+		let mut errors = ::std::vec::Vec::new();
+		#(#reads)*
+		#deny
+		#private::finish(errors)?;
+		::std::result::Result::Ok(#constructor { #(#inits),* })
+	})
+}
+
+/// Read a single variant out of `value`
+fn variant(container: &attr::Container, variant: &syn::Variant, tag: Option<&str>) -> Result<TokenStream> {
+	let krate = &container.krate;
+	let ident = &variant.ident;
+
+	match &variant.fields {
+		Fields::Unit if container.untagged => Ok(quote! {
+			match value {
+				#krate::value::Value::Null(_) => ::std::result::Result::Ok(Self::#ident),
+				_ => ::std::result::Result::Err(::std::vec![#krate::value::ConversionError::InvalidType(value.span(), "null", value.kind_name())]),
+			}
+		}),
+		Fields::Unit => Ok(quote!(::std::result::Result::Ok(Self::#ident))),
+		Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+			let ty = &fields.unnamed[0].ty;
+			Ok(quote!(<#ty as #krate::value::FromValue>::from_value(value).map(Self::#ident)))
+		}
+		Fields::Named(fields) => named_fields(container, fields, quote!(Self::#ident), tag),
+		_ => Err(Error::new_spanned(variant, "only variants with named fields or a single unnamed field are supported")),
+	}
+}
+
+fn enumeration(container: &attr::Container, name: &syn::Ident, data: &syn::DataEnum) -> Result<TokenStream> {
+	let krate = &container.krate;
+	let private = quote!(#krate::value::__private);
+	let error = quote!(#krate::value::ConversionError);
+
+	if container.untagged {
+		let attempts = data.variants.iter().map(|x| variant(container, x, None)).collect::<Result<Vec<_>>>()?;
+		let name = name.to_string();
+
+		return Ok(quote! {
+			let attempts: &[fn(&#krate::value::Value) -> ::std::result::Result<Self, ::std::vec::Vec<#error>>] = &[
+				#(|value| { #attempts },)*
+			];
+			let mut errors = ::std::vec::Vec::new();
+			for attempt in attempts {
+				match attempt(value) {
+					::std::result::Result::Ok(result) => return ::std::result::Result::Ok(result),
+					::std::result::Result::Err(error) => errors.extend(error),
+				}
+			}
+			::std::result::Result::Err(::std::vec![#error::NoMatchingVariant(value.span(), #name, errors)])
+		});
+	}
+
+	let mut keys = Vec::new();
+	let mut bodies = Vec::new();
+	for x in &data.variants {
+		let attr = attr::Variant::parse(&x.attrs)?;
+		if container.tag.is_none() && !matches!(x.fields, Fields::Unit) {
+			return Err(Error::new_spanned(x, "enum with data needs #[json(tag = \"...\")] or #[json(untagged)]"));
+		}
+		keys.push(key(&attr.rename, &x.ident));
+		bodies.push(variant(container, x, container.tag.as_deref())?);
+	}
+
+	let read = match &container.tag {
+		Some(tag) => quote!(#private::tag(#private::object(value)?, #tag)?),
+		None => quote!(#private::variant(value)?),
+	};
+
+	Ok(quote! {
+		let (span, name) = #read;
+		match name.as_str() {
+			#(#keys => { #bodies })*
+			_ => ::std::result::Result::Err(::std::vec![#error::UnknownVariant(span, name)]),
+		}
+	})
+}
@@ -0,0 +1,267 @@
+use crate::lexer::{Token, TokenKind};
+use crate::parser::ParseError;
+use crate::util::unescape;
+
+mod tree;
+use tree::{Builder, Container, Entry, Node};
+
+/// What each level of nesting is indented with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Indent {
+	Spaces(usize),
+	Tabs,
+}
+
+/// When to put a comma after the last entry of a multi-line object or array
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrailingComma {
+	Never,
+	/// Only valid in JSONC
+	Always,
+	/// Keep whatever the source had
+	Preserve,
+}
+
+/// How [format()](fn.format.html) lay out the source, use struct update syntax to only change what you care about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Style {
+	pub indent: Indent,
+	/// Arrays of only strings, numbers, booleans and null are kept on one line if the line doesn't go over this many characters
+	pub max_width: usize,
+	/// Sort object members by their unescaped key, comments move along with their member
+	pub sort_keys: bool,
+	pub trailing_comma: TrailingComma,
+	/// Maximum amount of nested objects and arrays, the formatter is recursive unlike [Parser](../parser/struct.Parser.html)
+	pub max_depth: usize,
+}
+
+impl Default for Style {
+	fn default() -> Style {
+		Style {
+			indent: Indent::Spaces(2),
+			max_width: 80,
+			sort_keys: false,
+			trailing_comma: TrailingComma::Never,
+			max_depth: 256,
+		}
+	}
+}
+
+/// Reformat `source` according to `style` while keeping every `//` and `/* */` comment
+///
+/// Only whitespace, commas and the order of members (with [Style::sort_keys](struct.Style.html#structfield.sort_keys)) are changed,
+/// every string and number is written exactly as it is in the source.
+///
+/// ```
+/// # use json_peek::format::{self, Style, TrailingComma};
+/// let source = r#"{ "b": [1, 2,3], // numbers
+///   /* letters */ "a": {"x": "y"} }"#;
+///
+/// let style = Style {
+///     sort_keys: true,
+///     trailing_comma: TrailingComma::Always,
+///     ..Style::default()
+/// };
+///
+/// assert_eq!(format::format(source, &style).unwrap(), r#"{
+///   /* letters */
+///   "a": {
+///     "x": "y",
+///   },
+///   "b": [1, 2, 3], // numbers
+/// }
+/// "#);
+/// ```
+pub fn format<'a>(source: &'a str, style: &Style) -> Result<String, ParseError<'a>> {
+	let document = Builder::new(source, style.max_depth).document()?;
+
+	let mut printer = Printer {
+		style,
+		output: String::with_capacity(source.len()),
+		depth: 0,
+	};
+	printer.entry(&document.root, false);
+	for comment in &document.dangling {
+		printer.newline();
+		printer.comment(comment);
+	}
+	printer.output.push('\n');
+
+	Ok(printer.output)
+}
+
+/// Text of `token` as it appear in the source
+fn text(token: &Token) -> String {
+	match token.kind {
+		TokenKind::String => format!("\"{}\"", token.value()),
+		_ => token.value().to_owned(),
+	}
+}
+
+struct Printer<'s> {
+	style: &'s Style,
+	output: String,
+	depth: usize,
+}
+
+impl Printer<'_> {
+	fn newline(&mut self) {
+		self.output.push('\n');
+		for _ in 0..self.depth {
+			match self.style.indent {
+				Indent::Spaces(width) => self.output.push_str(&" ".repeat(width)),
+				Indent::Tabs => self.output.push('\t'),
+			}
+		}
+	}
+
+	/// Characters on the current line so far
+	fn column(&self) -> usize {
+		self.output.rsplit('\n').next().map_or(0, |x| x.chars().count())
+	}
+
+	fn comment(&mut self, token: &Token) {
+		self.output.push_str(token.value().trim_end());
+	}
+
+	fn entry(&mut self, entry: &Entry, comma: bool) {
+		for comment in &entry.leading {
+			self.comment(comment);
+			self.newline();
+		}
+		if let Some(key) = &entry.key {
+			self.output.push_str(&text(key));
+			self.output.push_str(": ");
+		}
+		self.node(&entry.value);
+		if comma {
+			self.output.push(',');
+		}
+		if let Some(comment) = &entry.trailing {
+			self.output.push(' ');
+			self.comment(comment);
+		}
+	}
+
+	fn node(&mut self, node: &Node) {
+		match node {
+			Node::Scalar(token) => self.output.push_str(&text(token)),
+			Node::Array(container) => {
+				if let Some(compact) = self.compact(node, container) {
+					self.output.push_str(&compact);
+				}
+				else {
+					self.container(container, container.entries.iter().collect(), '[', ']');
+				}
+			}
+			Node::Object(container) => {
+				let mut entries: Vec<_> = container.entries.iter().collect();
+				if self.style.sort_keys {
+					entries.sort_by_cached_key(|x| x.key.map(|key| unescape(key.value()).into_owned()));
+				}
+				self.container(container, entries, '{', '}');
+			}
+		}
+	}
+
+	/// One line form of an array if it's small enough
+	fn compact(&self, node: &Node, container: &Container) -> Option<String> {
+		if node.has_comments() {
+			return None;
+		}
+
+		let mut elements = Vec::new();
+		for entry in &container.entries {
+			match &entry.value {
+				Node::Scalar(token) => elements.push(text(token)),
+				_ => return None,
+			}
+		}
+
+		let compact = format!("[{}]", elements.join(", "));
+		if self.column() + compact.chars().count() <= self.style.max_width {
+			Some(compact)
+		}
+		else {
+			None
+		}
+	}
+
+	fn container(&mut self, container: &Container, entries: Vec<&Entry>, open: char, close: char) {
+		self.output.push(open);
+		if entries.is_empty() && container.dangling.is_empty() {
+			self.output.push(close);
+			return;
+		}
+
+		let trailing_comma = match self.style.trailing_comma {
+			TrailingComma::Never => false,
+			TrailingComma::Always => true,
+			TrailingComma::Preserve => container.trailing_comma,
+		};
+
+		self.depth += 1;
+		for (index, entry) in entries.iter().enumerate() {
+			self.newline();
+			self.entry(entry, index + 1 < entries.len() || trailing_comma);
+		}
+		for comment in &container.dangling {
+			self.newline();
+			self.comment(comment);
+		}
+		self.depth -= 1;
+
+		self.newline();
+		self.output.push(close);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::util::Span;
+
+	#[test]
+	fn keep_comments() {
+		let source = "// header\n{\"a\": [ // first\n1,\n// before two\n2, ],\n\"b\": {/* empty */}, \"c\": [] } // end\n/* footer */";
+		let style = Style {
+			indent: Indent::Tabs,
+			trailing_comma: TrailingComma::Preserve,
+			..Style::default()
+		};
+
+		assert_eq!(
+			format(source, &style).unwrap(),
+			"// header\n{\n\t\"a\": [\n\t\t// first\n\t\t1,\n\t\t// before two\n\t\t2,\n\t],\n\t\"b\": {\n\t\t/* empty */\n\t},\n\t\"c\": []\n} // end\n/* footer */\n"
+		);
+	}
+
+	#[test]
+	fn compact_arrays() {
+		let source = r#"{"short": [1, 2, 3], "long": ["aaaaaaaa", "bbbbbbbb"], "nested": [[1]]}"#;
+		let style = Style {
+			max_width: 30,
+			..Style::default()
+		};
+
+		assert_eq!(
+			format(source, &style).unwrap(),
+			"{\n  \"short\": [1, 2, 3],\n  \"long\": [\n    \"aaaaaaaa\",\n    \"bbbbbbbb\"\n  ],\n  \"nested\": [\n    [1]\n  ]\n}\n"
+		);
+	}
+
+	#[test]
+	fn invalid_source() {
+		assert!(matches!(format("[1 2]", &Style::default()), Err(ParseError::UnexpectedToken(..))));
+		assert!(matches!(format("[1] 2", &Style::default()), Err(ParseError::InvalidToken(..))));
+		assert!(matches!(format("{a: true}", &Style::default()), Err(ParseError::InvalidToken(..))));
+		assert!(matches!(format(r#"{"a": bar}"#, &Style::default()), Err(ParseError::InvalidToken(..))));
+		assert!(matches!(format("[bar]", &Style::default()), Err(ParseError::InvalidToken(..))));
+
+		let style = Style {
+			max_depth: 2,
+			..Style::default()
+		};
+		assert_eq!(format("[[[]]]", &style), Err(ParseError::DepthLimitExceeded(Span::new(2, 3))));
+	}
+}
@@ -0,0 +1,204 @@
+use crate::lexer::{ByteLexerIter, Lexer, Token, TokenKind};
+use crate::parser::ParseError;
+use crate::util::Span;
+use std::iter::Peekable;
+
+/// Value along with the comments around it, built straight from tokens so nothing is lost
+pub(super) enum Node<'a> {
+	Scalar(Token<'a>),
+	Object(Container<'a>),
+	Array(Container<'a>),
+}
+
+#[derive(Default)]
+pub(super) struct Container<'a> {
+	pub entries: Vec<Entry<'a>>,
+	/// Comments after the last entry
+	pub dangling: Vec<Token<'a>>,
+	pub trailing_comma: bool,
+}
+
+/// Member of an object or element of an array
+pub(super) struct Entry<'a> {
+	/// Comments before this entry
+	pub leading: Vec<Token<'a>>,
+	pub key: Option<Token<'a>>,
+	pub value: Node<'a>,
+	/// Comment on the same line right after this entry
+	pub trailing: Option<Token<'a>>,
+}
+
+impl Node<'_> {
+	pub fn has_comments(&self) -> bool {
+		match self {
+			Node::Scalar(_) => false,
+			Node::Object(container) | Node::Array(container) => {
+				!container.dangling.is_empty()
+					|| container
+						.entries
+						.iter()
+						.any(|x| !x.leading.is_empty() || x.trailing.is_some() || x.value.has_comments())
+			}
+		}
+	}
+}
+
+/// The whole source, comments after the value are kept as dangling
+pub(super) struct Document<'a> {
+	pub root: Entry<'a>,
+	pub dangling: Vec<Token<'a>>,
+}
+
+pub(super) struct Builder<'a> {
+	source: &'a str,
+	tokens: Peekable<ByteLexerIter<'a>>,
+	/// Position of the last token, used to check if a comment is on the same line
+	pos: Span,
+	depth: usize,
+	max_depth: usize,
+}
+
+impl<'a> Builder<'a> {
+	pub fn new(source: &'a str, max_depth: usize) -> Builder<'a> {
+		Builder {
+			source,
+			tokens: Lexer::new(source).byte_iter().peekable(),
+			pos: Span::default(),
+			depth: 0,
+			max_depth,
+		}
+	}
+
+	pub fn document(mut self) -> Result<Document<'a>, ParseError<'a>> {
+		let leading = self.comments();
+		let token = self.next()?;
+		let value = self.node(token)?;
+		let trailing = self.trailing_comment();
+		let dangling = self.comments();
+
+		if self.tokens.peek().is_some() {
+			let token = self.next()?;
+			return Err(ParseError::InvalidToken(token, Vec::new()));
+		}
+
+		Ok(Document {
+			root: Entry {
+				leading,
+				key: None,
+				value,
+				trailing,
+			},
+			dangling,
+		})
+	}
+
+	fn next(&mut self) -> Result<Token<'a>, ParseError<'a>> {
+		let token = self
+			.tokens
+			.next()
+			.ok_or(ParseError::UnexpectedEndOfFile(self.pos, Vec::new()))?;
+		self.pos = token.full_span();
+		match token.kind {
+			TokenKind::Error(error) => Err(ParseError::MalformedToken(token.span, error)),
+			_ => Ok(token),
+		}
+	}
+
+	/// Take every comment until the next token
+	fn comments(&mut self) -> Vec<Token<'a>> {
+		let mut comments = Vec::new();
+		while let Some(token) = self.tokens.next_if(|x| x.kind == TokenKind::Comment) {
+			self.pos = token.span;
+			comments.push(token);
+		}
+		comments
+	}
+
+	/// Take the next comment only if it's on the same line as the last token
+	fn trailing_comment(&mut self) -> Option<Token<'a>> {
+		let (source, end) = (self.source, self.pos.end);
+		let token = self
+			.tokens
+			.next_if(|x| x.kind == TokenKind::Comment && !source[end..x.span.start].contains('\n'))?;
+		self.pos = token.span;
+		Some(token)
+	}
+
+	fn node(&mut self, token: Token<'a>) -> Result<Node<'a>, ParseError<'a>> {
+		match token.kind {
+			TokenKind::String | TokenKind::Number => Ok(Node::Scalar(token)),
+			TokenKind::Identifier if matches!(token.value(), "true" | "false" | "null") => Ok(Node::Scalar(token)),
+			TokenKind::Symbol if token.is_symbol('{') => Ok(Node::Object(self.container(token, '}')?)),
+			TokenKind::Symbol if token.is_symbol('[') => Ok(Node::Array(self.container(token, ']')?)),
+			_ => Err(ParseError::InvalidToken(token, Vec::new())),
+		}
+	}
+
+	fn container(&mut self, open: Token<'a>, closing: char) -> Result<Container<'a>, ParseError<'a>> {
+		if self.depth >= self.max_depth {
+			return Err(ParseError::DepthLimitExceeded(open.span));
+		}
+		self.depth += 1;
+
+		let mut container = Container::default();
+		let mut pending = Vec::new();
+
+		loop {
+			pending.extend(self.comments());
+			let mut token = self.next()?;
+			if token.is_symbol(closing) {
+				container.dangling = pending;
+				break;
+			}
+
+			let mut leading = std::mem::take(&mut pending);
+			let key = if closing == '}' {
+				if token.kind != TokenKind::String {
+					return Err(ParseError::InvalidToken(token, Vec::new()));
+				}
+				let key = token;
+
+				leading.extend(self.comments());
+				let colon = self.next()?;
+				if !colon.is_symbol(':') {
+					return Err(ParseError::UnexpectedToken(colon, Token::test_symbol(":"), Vec::new()));
+				}
+				leading.extend(self.comments());
+				token = self.next()?;
+				Some(key)
+			}
+			else {
+				None
+			};
+
+			let value = self.node(token)?;
+			let mut trailing = self.trailing_comment();
+			pending.extend(self.comments());
+
+			let token = self.next()?;
+			let is_comma = token.is_symbol(',');
+			if is_comma && trailing.is_none() && pending.is_empty() {
+				trailing = self.trailing_comment();
+			}
+			container.entries.push(Entry {
+				leading,
+				key,
+				value,
+				trailing,
+			});
+
+			if token.is_symbol(closing) {
+				container.trailing_comma = false;
+				container.dangling = std::mem::take(&mut pending);
+				break;
+			}
+			else if !is_comma {
+				return Err(ParseError::UnexpectedToken(token, Token::test_symbol(","), Vec::new()));
+			}
+			container.trailing_comma = true;
+		}
+
+		self.depth -= 1;
+		Ok(container)
+	}
+}
@@ -0,0 +1,34 @@
+use crate::util::Span;
+use thiserror::Error;
+
+/// Represent possible error that can happen while parsing a JSONPath query, spans are relative to the query string
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+pub enum PathError {
+	/// Query contain a character that isn't allowed at this position
+	#[error("Unexpected character {1:?} at {0}")]
+	UnexpectedCharacter(Span, char),
+
+	/// Query end while something else is expected
+	#[error("Unexpected end of query at {0}")]
+	UnexpectedEnd(Span),
+
+	/// Number has leading zero, is `-0` where an integer is expected or is outside of `±(2^53 - 1)`
+	#[error("Invalid number {1:?} at {0}")]
+	InvalidNumber(Span, String),
+
+	/// String literal contain an escape sequence that doesn't exist
+	#[error("Invalid escape sequence at {0}")]
+	InvalidEscape(Span),
+
+	/// Function isn't one of `length`, `count`, `match`, `search` or `value`
+	#[error("Unknown function {1:?} at {0}")]
+	UnknownFunction(Span, String),
+
+	/// Function is called with the wrong amount of arguments, contain the amount that was expected
+	#[error("Expected {1} argument(s) at {0}")]
+	ArgumentCount(Span, usize),
+
+	/// Expression is well-formed but doesn't have the right type, for example comparing with a query that can select more than one node
+	#[error("Expected {1} at {0}")]
+	TypeMismatch(Span, &'static str),
+}
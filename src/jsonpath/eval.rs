@@ -0,0 +1,415 @@
+use super::syntax::{Argument, Comparable, Comparison, Constant, Function, FunctionKind, Logical, Query, Segment, Selector};
+use super::{Match, NormalizedPath, PathElement};
+use crate::util::unescape;
+use crate::value::Value;
+use regex::Regex;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Node that is being visited along with the path to get there
+struct Node<'v> {
+	path: Vec<PathElement>,
+	value: &'v Value,
+}
+
+impl<'v> Node<'v> {
+	fn child(&self, element: PathElement, value: &'v Value) -> Node<'v> {
+		let mut path = self.path.clone();
+		path.push(element);
+		Node { path, value }
+	}
+
+	/// Children in document order, object members are ordered by their position in the source
+	fn children(&self) -> Vec<Node<'v>> {
+		match self.value {
+			Value::Array(array) => array
+				.value
+				.iter()
+				.enumerate()
+				.map(|(index, value)| self.child(PathElement::Index(index), value))
+				.collect(),
+			Value::Object(object) => object
+				.iter()
+				.map(|(key, value)| self.child(PathElement::Name(unescape(&key.value).into_owned()), value))
+				.collect(),
+			_ => Vec::new(),
+		}
+	}
+}
+
+/// Value that can be compared inside of a filter
+#[derive(Debug, Clone)]
+enum Operand<'a> {
+	/// Result of a singular query that doesn't select anything
+	Nothing,
+	Null,
+	Bool(bool),
+	Number(f64),
+	String(Cow<'a, str>),
+	/// Array or object
+	Node(&'a Value),
+}
+
+impl<'a> Operand<'a> {
+	fn from_value(value: &'a Value) -> Operand<'a> {
+		match value {
+			Value::Null(_) => Operand::Null,
+			Value::Bool(item) => Operand::Bool(item.value),
+			Value::Number(item) => Operand::Number(item.value.parse().unwrap_or(f64::NAN)),
+			Value::Literal(item) => Operand::String(unescape(&item.value)),
+			Value::Array(_) | Value::Object(_) => Operand::Node(value),
+		}
+	}
+
+	fn from_constant(constant: &'a Constant) -> Operand<'a> {
+		match constant {
+			Constant::Null => Operand::Null,
+			Constant::Bool(value) => Operand::Bool(*value),
+			Constant::Number(value) => Operand::Number(*value),
+			Constant::String(value) => Operand::String(Cow::Borrowed(value)),
+		}
+	}
+}
+
+/// Result of a function call
+enum Evaluated<'a> {
+	Value(Operand<'a>),
+	Logical(bool),
+}
+
+pub(super) struct Evaluator<'v> {
+	root: &'v Value,
+	/// Compiled patterns of `match()` and `search()` by their source, a filter is tested against every node
+	/// so each pattern is only compiled the first time it's used
+	match_patterns: RefCell<HashMap<String, Option<Regex>>>,
+	search_patterns: RefCell<HashMap<String, Option<Regex>>>,
+}
+
+impl<'v> Evaluator<'v> {
+	pub fn new(root: &'v Value) -> Evaluator<'v> {
+		Evaluator {
+			root,
+			match_patterns: RefCell::default(),
+			search_patterns: RefCell::default(),
+		}
+	}
+
+	pub fn matches(&self, query: &Query) -> Vec<Match<'v>> {
+		self.query(query, self.root)
+			.into_iter()
+			.map(|node| Match {
+				path: NormalizedPath::from(node.path),
+				value: node.value,
+			})
+			.collect()
+	}
+
+	fn query(&self, query: &Query, current: &'v Value) -> Vec<Node<'v>> {
+		let start = if query.is_root { self.root } else { current };
+		let mut nodes = vec![Node {
+			path: Vec::new(),
+			value: start,
+		}];
+
+		for segment in &query.segments {
+			let mut next = Vec::new();
+			for node in &nodes {
+				match segment {
+					Segment::Child(selectors) => self.select_all(selectors, node, &mut next),
+					Segment::Descendant(selectors) => self.descend(selectors, node, &mut next),
+				}
+			}
+			nodes = next;
+		}
+
+		nodes
+	}
+
+	/// Apply `selectors` to `node` and all of its descendants, this doesn't recurse so deep input can't overflow the stack
+	fn descend(&self, selectors: &[Selector], node: &Node<'v>, output: &mut Vec<Node<'v>>) {
+		self.select_all(selectors, node, output);
+
+		let mut stack = node.children();
+		stack.reverse();
+
+		while let Some(node) = stack.pop() {
+			self.select_all(selectors, &node, output);
+			stack.extend(node.children().into_iter().rev());
+		}
+	}
+
+	fn select_all(&self, selectors: &[Selector], node: &Node<'v>, output: &mut Vec<Node<'v>>) {
+		for selector in selectors {
+			self.select(selector, node, output);
+		}
+	}
+
+	fn select(&self, selector: &Selector, node: &Node<'v>, output: &mut Vec<Node<'v>>) {
+		match (selector, node.value) {
+			(Selector::Name(name), Value::Object(object)) => {
+				let member = object.value.iter().find(|(key, _)| unescape(&key.value) == name.as_str());
+				if let Some((_, value)) = member {
+					output.push(node.child(PathElement::Name(name.clone()), value));
+				}
+			}
+			(Selector::Wildcard, _) => output.extend(node.children()),
+			(Selector::Index(index), Value::Array(array)) => {
+				let len = array.value.len() as i64;
+				let index = if *index < 0 { len + index } else { *index };
+
+				if (0..len).contains(&index) {
+					let index = index as usize;
+					output.push(node.child(PathElement::Index(index), &array.value[index]));
+				}
+			}
+			(Selector::Slice(start, end, step), Value::Array(array)) => {
+				for index in slice(array.value.len(), *start, *end, step.unwrap_or(1)) {
+					output.push(node.child(PathElement::Index(index), &array.value[index]));
+				}
+			}
+			(Selector::Filter(logical), _) => {
+				let children = node.children().into_iter().filter(|child| self.test(logical, child.value));
+				output.extend(children);
+			}
+			_ => {}
+		}
+	}
+
+	fn test(&self, logical: &Logical, current: &'v Value) -> bool {
+		match logical {
+			Logical::Or(items) => items.iter().any(|x| self.test(x, current)),
+			Logical::And(items) => items.iter().all(|x| self.test(x, current)),
+			Logical::Not(inner) => !self.test(inner, current),
+			Logical::Exists(query) => !self.query(query, current).is_empty(),
+			Logical::Function(function) => match self.call(function, current) {
+				Evaluated::Logical(value) => value,
+				Evaluated::Value(_) => false,
+			},
+			Logical::Compare(left, comparison, right) => {
+				let left = self.comparable(left, current);
+				let right = self.comparable(right, current);
+				compare(&left, *comparison, &right)
+			}
+		}
+	}
+
+	fn comparable<'a>(&self, comparable: &'a Comparable, current: &'v Value) -> Operand<'a>
+	where
+		'v: 'a,
+	{
+		match comparable {
+			Comparable::Constant(constant) => Operand::from_constant(constant),
+			Comparable::Query(query) => self.singular(query, current),
+			Comparable::Function(function) => match self.call(function, current) {
+				Evaluated::Value(value) => value,
+				Evaluated::Logical(_) => Operand::Nothing,
+			},
+		}
+	}
+
+	fn singular(&self, query: &Query, current: &'v Value) -> Operand<'v> {
+		match self.query(query, current).first() {
+			Some(node) => Operand::from_value(node.value),
+			None => Operand::Nothing,
+		}
+	}
+
+	/// Argument of a function that expect a single value
+	fn value_argument<'a>(&self, argument: &'a Argument, current: &'v Value) -> Operand<'a>
+	where
+		'v: 'a,
+	{
+		match argument {
+			Argument::Constant(constant) => Operand::from_constant(constant),
+			Argument::Query(query) => self.singular(query, current),
+			Argument::Function(function) => match self.call(function, current) {
+				Evaluated::Value(value) => value,
+				Evaluated::Logical(_) => Operand::Nothing,
+			},
+			Argument::Logical(_) => Operand::Nothing,
+		}
+	}
+
+	/// Argument of a function that expect a list of nodes
+	fn nodes_argument(&self, argument: &Argument, current: &'v Value) -> Vec<&'v Value> {
+		match argument {
+			Argument::Query(query) => self.query(query, current).into_iter().map(|x| x.value).collect(),
+			_ => Vec::new(),
+		}
+	}
+
+	fn call<'a>(&self, function: &'a Function, current: &'v Value) -> Evaluated<'a>
+	where
+		'v: 'a,
+	{
+		let args = &function.args;
+
+		match function.kind {
+			FunctionKind::Length => {
+				let length = match self.value_argument(&args[0], current) {
+					Operand::String(value) => value.chars().count(),
+					Operand::Node(Value::Array(array)) => array.value.len(),
+					Operand::Node(Value::Object(object)) => object.value.len(),
+					_ => return Evaluated::Value(Operand::Nothing),
+				};
+				Evaluated::Value(Operand::Number(length as f64))
+			}
+			FunctionKind::Count => {
+				let count = self.nodes_argument(&args[0], current).len();
+				Evaluated::Value(Operand::Number(count as f64))
+			}
+			FunctionKind::Value => {
+				let nodes = self.nodes_argument(&args[0], current);
+				match nodes.as_slice() {
+					[value] => Evaluated::Value(Operand::from_value(value)),
+					_ => Evaluated::Value(Operand::Nothing),
+				}
+			}
+			FunctionKind::Match | FunctionKind::Search => {
+				let value = self.value_argument(&args[0], current);
+				let pattern = self.value_argument(&args[1], current);
+
+				let is_match = match (value, pattern) {
+					(Operand::String(value), Operand::String(pattern)) => self.is_match(function.kind, &pattern, &value),
+					_ => false,
+				};
+				Evaluated::Logical(is_match)
+			}
+		}
+	}
+
+	/// Test `value` against `pattern` for `match()` or `search()`, an invalid pattern never match
+	fn is_match(&self, kind: FunctionKind, pattern: &str, value: &str) -> bool {
+		let (cache, is_full) = match kind {
+			FunctionKind::Match => (&self.match_patterns, true),
+			_ => (&self.search_patterns, false),
+		};
+
+		let mut cache = cache.borrow_mut();
+		if !cache.contains_key(pattern) {
+			cache.insert(pattern.to_owned(), regex(pattern, is_full));
+		}
+		matches!(&cache[pattern], Some(regex) if regex.is_match(value))
+	}
+}
+
+/// Indices selected by `[start:end:step]`, following section 2.3.4.2.2 of RFC 9535
+fn slice(len: usize, start: Option<i64>, end: Option<i64>, step: i64) -> Vec<usize> {
+	let len = len as i64;
+	let normalize = |index: i64| if index >= 0 { index } else { len + index };
+
+	match step.cmp(&0) {
+		Ordering::Equal => Vec::new(),
+		Ordering::Greater => {
+			let lower = start.map_or(0, normalize).max(0).min(len);
+			let upper = end.map_or(len, normalize).max(0).min(len);
+			(lower..upper).step_by(step as usize).map(|x| x as usize).collect()
+		}
+		Ordering::Less => {
+			let upper = start.map_or(len - 1, normalize).max(-1).min(len - 1);
+			let lower = end.map_or(-1, normalize).max(-1).min(len - 1);
+
+			let mut result = Vec::new();
+			let mut index = upper;
+			while index > lower {
+				result.push(index as usize);
+				index += step;
+			}
+			result
+		}
+	}
+}
+
+fn compare(left: &Operand, comparison: Comparison, right: &Operand) -> bool {
+	match comparison {
+		Comparison::Equal => equal(left, right),
+		Comparison::NotEqual => !equal(left, right),
+		Comparison::Less => less(left, right),
+		Comparison::LessEqual => less(left, right) || equal(left, right),
+		Comparison::Greater => less(right, left),
+		Comparison::GreaterEqual => less(right, left) || equal(left, right),
+	}
+}
+
+fn equal(left: &Operand, right: &Operand) -> bool {
+	match (left, right) {
+		(Operand::Nothing, Operand::Nothing) | (Operand::Null, Operand::Null) => true,
+		(Operand::Bool(a), Operand::Bool(b)) => a == b,
+		(Operand::Number(a), Operand::Number(b)) => a == b,
+		(Operand::String(a), Operand::String(b)) => a == b,
+		(Operand::Node(a), Operand::Node(b)) => deep_equal(a, b),
+		_ => false,
+	}
+}
+
+fn less(left: &Operand, right: &Operand) -> bool {
+	match (left, right) {
+		(Operand::Number(a), Operand::Number(b)) => a < b,
+		(Operand::String(a), Operand::String(b)) => a < b,
+		_ => false,
+	}
+}
+
+fn deep_equal(left: &Value, right: &Value) -> bool {
+	match (left, right) {
+		(Value::Array(a), Value::Array(b)) => {
+			a.value.len() == b.value.len() && a.value.iter().zip(&b.value).all(|(a, b)| deep_equal(a, b))
+		}
+		(Value::Object(a), Value::Object(b)) => {
+			a.value.len() == b.value.len()
+				&& a.value.iter().all(|(key, a)| matches!(b.value.get(key), Some(b) if deep_equal(a, b)))
+		}
+		_ => equal(&Operand::from_value(left), &Operand::from_value(right)),
+	}
+}
+
+/// Compile I-Regexp (RFC 9485) pattern, `.` doesn't match line breaks there
+fn regex(pattern: &str, is_full: bool) -> Option<Regex> {
+	let mut translated = String::new();
+	let mut in_class = false;
+	let mut chars = pattern.chars();
+
+	while let Some(current) = chars.next() {
+		match current {
+			'\\' => {
+				translated.push(current);
+				translated.extend(chars.next());
+			}
+			'[' => {
+				in_class = true;
+				translated.push(current);
+			}
+			']' => {
+				in_class = false;
+				translated.push(current);
+			}
+			'.' if !in_class => translated.push_str("[^\\n\\r]"),
+			_ => translated.push(current),
+		}
+	}
+
+	if is_full {
+		translated = format!("^(?:{})$", translated);
+	}
+	Regex::new(&translated).ok()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::jsonpath::JsonPath;
+	use crate::util;
+
+	#[test]
+	fn compile_pattern_once() {
+		let value = util::from_str(r#"["a1", "b2", "a3", "c", "ab"]"#).unwrap();
+		let query = JsonPath::parse("$[?match(@, 'a.') || search(@, 'b')]").unwrap();
+
+		let evaluator = Evaluator::new(&value);
+		assert_eq!(evaluator.matches(&query.query).len(), 4);
+		assert_eq!(evaluator.match_patterns.borrow().len(), 1);
+		assert_eq!(evaluator.search_patterns.borrow().len(), 1);
+	}
+}
@@ -0,0 +1,211 @@
+use crate::util::Span;
+use crate::value::Value;
+use std::fmt;
+use std::str::FromStr;
+
+mod error;
+mod eval;
+mod syntax;
+
+pub use error::PathError;
+
+/// One step inside of a [NormalizedPath](struct.NormalizedPath.html)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PathElement {
+	/// Member of an object, already unescaped
+	Name(String),
+	/// Item of an array
+	Index(usize),
+}
+
+/// Path that identify exactly one node, displayed in the normalized form of RFC 9535 (`$['criteria'][0]`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct NormalizedPath(Vec<PathElement>);
+
+impl NormalizedPath {
+	pub fn elements(&self) -> &[PathElement] {
+		&self.0
+	}
+}
+
+impl From<Vec<PathElement>> for NormalizedPath {
+	fn from(elements: Vec<PathElement>) -> NormalizedPath {
+		NormalizedPath(elements)
+	}
+}
+
+impl fmt::Display for NormalizedPath {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "$")?;
+
+		for element in &self.0 {
+			match element {
+				PathElement::Index(index) => write!(f, "[{}]", index)?,
+				PathElement::Name(name) => {
+					write!(f, "['")?;
+					for current in name.chars() {
+						match current {
+							'\u{8}' => write!(f, "\\b")?,
+							'\u{c}' => write!(f, "\\f")?,
+							'\n' => write!(f, "\\n")?,
+							'\r' => write!(f, "\\r")?,
+							'\t' => write!(f, "\\t")?,
+							'\'' => write!(f, "\\'")?,
+							'\\' => write!(f, "\\\\")?,
+							_ if current < ' ' => write!(f, "\\u{:04x}", current as u32)?,
+							_ => write!(f, "{}", current)?,
+						}
+					}
+					write!(f, "']")?;
+				}
+			}
+		}
+
+		Ok(())
+	}
+}
+
+/// Node selected by a [JsonPath](struct.JsonPath.html)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match<'v> {
+	pub path: NormalizedPath,
+	pub value: &'v Value,
+}
+
+impl Match<'_> {
+	pub fn span(&self) -> Span {
+		self.value.span()
+	}
+}
+
+/// Compiled JSONPath query as described in [RFC 9535](https://www.rfc-editor.org/rfc/rfc9535)
+///
+/// Matches are returned in document order, members of an object are ordered by where they appear in the source.
+///
+/// ```
+/// # use json_peek::util;
+/// # use json_peek::util::Span;
+/// # use json_peek::jsonpath::JsonPath;
+/// let value = util::from_str(r#"{ "criteria": { "a": { "trigger": "minecraft:tick" } } }"#).unwrap();
+/// let path = JsonPath::parse("$..trigger").unwrap();
+///
+/// let matches = path.query(&value);
+/// assert_eq!(matches[0].path.to_string(), "$['criteria']['a']['trigger']");
+/// assert_eq!(matches[0].span(), Span::new(34, 50));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonPath {
+	query: syntax::Query,
+}
+
+impl JsonPath {
+	pub fn parse(query: &str) -> Result<JsonPath, PathError> {
+		syntax::Parser::parse(query).map(|query| JsonPath { query })
+	}
+
+	/// Select every node in `value` that match this query
+	pub fn query<'v>(&self, value: &'v Value) -> Vec<Match<'v>> {
+		eval::Evaluator::new(value).matches(&self.query)
+	}
+}
+
+impl FromStr for JsonPath {
+	type Err = PathError;
+
+	fn from_str(query: &str) -> Result<JsonPath, PathError> {
+		JsonPath::parse(query)
+	}
+}
+
+impl Value {
+	/// Parse `query` as a [JsonPath](../jsonpath/struct.JsonPath.html) and run it on this value
+	pub fn query(&self, query: &str) -> Result<Vec<Match<'_>>, PathError> {
+		Ok(JsonPath::parse(query)?.query(self))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::util;
+
+	const STORE: &str = r#"
+	{ "store": {
+		"book": [
+			{ "category": "reference", "author": "Nigel Rees", "title": "Sayings of the Century", "price": 8.95 },
+			{ "category": "fiction", "author": "Evelyn Waugh", "title": "Sword of Honour", "price": 12.99 },
+			{ "category": "fiction", "author": "Herman Melville", "title": "Moby Dick", "isbn": "0-553-21311-3", "price": 8.99 },
+			{ "category": "fiction", "author": "J. R. R. Tolkien", "title": "The Lord of the Rings", "isbn": "0-395-19395-8", "price": 22.99 }
+		],
+		"bicycle": { "color": "red", "price": 399 }
+	} }
+	"#;
+
+	fn paths(query: &str) -> Vec<String> {
+		let value = util::from_str(STORE).unwrap();
+		value.query(query).unwrap().iter().map(|x| x.path.to_string()).collect()
+	}
+
+	#[test]
+	fn selectors() {
+		assert_eq!(paths("$.store.bicycle.color"), vec!["$['store']['bicycle']['color']"]);
+		assert_eq!(paths("$.store.*").len(), 2);
+		assert_eq!(paths("$['store'][\"book\"][-1].title"), vec!["$['store']['book'][3]['title']"]);
+		assert_eq!(paths("$.store.book[0, 2].price"), vec!["$['store']['book'][0]['price']", "$['store']['book'][2]['price']"]);
+		assert_eq!(paths("$.store.book[1:3].author").len(), 2);
+		assert_eq!(paths("$.store.book[::-2].author"), vec!["$['store']['book'][3]['author']", "$['store']['book'][1]['author']"]);
+		assert_eq!(paths("$.store.book[7]"), Vec::<String>::new());
+	}
+
+	#[test]
+	fn descendant() {
+		assert_eq!(paths("$..author").len(), 4);
+		assert_eq!(paths("$..price").len(), 5);
+		assert_eq!(paths("$.store..price")[4], "$['store']['bicycle']['price']");
+		assert_eq!(paths("$..book[2]"), vec!["$['store']['book'][2]"]);
+		assert_eq!(paths("$..*").len(), 27);
+	}
+
+	#[test]
+	fn filters() {
+		assert_eq!(paths("$..book[?@.isbn].title").len(), 2);
+		assert_eq!(paths("$..book[?@.price < 10].title").len(), 2);
+		assert_eq!(paths("$..book[?@.price < $.store.bicycle.price && @.category == 'fiction']").len(), 3);
+		assert_eq!(paths("$..book[?!(@.category != 'reference')]"), vec!["$['store']['book'][0]"]);
+		assert_eq!(paths("$..*[?@.color == \"red\" || @.price > 20]").len(), 2);
+		assert_eq!(paths("$.store.book[?length(@.title) > 15]").len(), 2);
+		assert_eq!(paths("$.store[?count(@.*) == 2]"), vec!["$['store']['bicycle']"]);
+		assert_eq!(paths("$..book[?match(@.author, 'J.*')]"), vec!["$['store']['book'][3]"]);
+		assert_eq!(paths("$..book[?search(@.title, 'of')]").len(), 3);
+		assert_eq!(paths("$..book[?value(@..isbn) == '0-553-21311-3']"), vec!["$['store']['book'][2]"]);
+	}
+
+	#[test]
+	fn spans() {
+		let content = r#"{"a": [1, {"b": "c"}]}"#;
+		let value = util::from_str(content).unwrap();
+		let matches = value.query("$.a[1].b").unwrap();
+
+		assert_eq!(matches.len(), 1);
+		assert_eq!(matches[0].span(), Span::new(16, 19));
+		assert_eq!(matches[0].value.source_text(content), Some("\"c\""));
+	}
+
+	#[test]
+	fn normalized_path() {
+		let value = util::from_str(r#"{"it's\n": 1}"#).unwrap();
+		assert_eq!(value.query("$.*").unwrap()[0].path.to_string(), r#"$['it\'s\n']"#);
+	}
+
+	#[test]
+	fn invalid_query() {
+		assert_eq!(JsonPath::parse("store"), Err(PathError::UnexpectedCharacter(Span::new(0, 1), 's')));
+		assert_eq!(JsonPath::parse("$.a["), Err(PathError::UnexpectedEnd(Span::new(4, 4))));
+		assert_eq!(JsonPath::parse("$[01]"), Err(PathError::InvalidNumber(Span::new(2, 4), "01".to_owned())));
+		assert_eq!(JsonPath::parse("$['\\q']"), Err(PathError::InvalidEscape(Span::new(3, 5))));
+		assert_eq!(JsonPath::parse("$[?foo(@)]"), Err(PathError::UnknownFunction(Span::new(3, 6), "foo".to_owned())));
+		assert_eq!(JsonPath::parse("$[?length(@, 1)]"), Err(PathError::ArgumentCount(Span::new(13, 14), 1)));
+		assert_eq!(JsonPath::parse("$[?@.* == 1]"), Err(PathError::TypeMismatch(Span::new(3, 6), "singular query")));
+		assert_eq!(JsonPath::parse("$[?length(@.a)]"), Err(PathError::TypeMismatch(Span::new(3, 14), "logical expression")));
+	}
+}
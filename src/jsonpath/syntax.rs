@@ -0,0 +1,695 @@
+use super::PathError;
+use crate::util::Span;
+
+/// Largest integer that I-JSON can represent exactly
+const MAX_INT: i64 = (1 << 53) - 1;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Query {
+	/// Start from the root (`$`) rather than the current node (`@`)
+	pub is_root: bool,
+	pub segments: Vec<Segment>,
+}
+
+impl Query {
+	/// Query that can only ever select at most one node
+	fn is_singular(&self) -> bool {
+		self.segments.iter().all(|segment| match segment {
+			Segment::Child(selectors) => {
+				selectors.len() == 1 && matches!(selectors[0], Selector::Name(_) | Selector::Index(_))
+			}
+			Segment::Descendant(_) => false,
+		})
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Segment {
+	Child(Vec<Selector>),
+	Descendant(Vec<Selector>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Selector {
+	Name(String),
+	Wildcard,
+	Index(i64),
+	Slice(Option<i64>, Option<i64>, Option<i64>),
+	Filter(Logical),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Logical {
+	Or(Vec<Logical>),
+	And(Vec<Logical>),
+	Not(Box<Logical>),
+	Compare(Comparable, Comparison, Comparable),
+	Exists(Query),
+	Function(Function),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Comparable {
+	Constant(Constant),
+	Query(Query),
+	Function(Function),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Constant {
+	Number(f64),
+	String(String),
+	Bool(bool),
+	Null,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Comparison {
+	Equal,
+	NotEqual,
+	Less,
+	LessEqual,
+	Greater,
+	GreaterEqual,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Function {
+	pub kind: FunctionKind,
+	pub args: Vec<Argument>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum FunctionKind {
+	Length,
+	Count,
+	Match,
+	Search,
+	Value,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Argument {
+	Constant(Constant),
+	Query(Query),
+	Logical(Logical),
+	Function(Function),
+}
+
+/// Type of function's parameters and results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Type {
+	Value,
+	Logical,
+	Nodes,
+}
+
+impl FunctionKind {
+	fn from_name(name: &str) -> Option<FunctionKind> {
+		let kind = match name {
+			"length" => FunctionKind::Length,
+			"count" => FunctionKind::Count,
+			"match" => FunctionKind::Match,
+			"search" => FunctionKind::Search,
+			"value" => FunctionKind::Value,
+			_ => return None,
+		};
+		Some(kind)
+	}
+
+	fn parameters(self) -> &'static [Type] {
+		match self {
+			FunctionKind::Length => &[Type::Value],
+			FunctionKind::Count | FunctionKind::Value => &[Type::Nodes],
+			FunctionKind::Match | FunctionKind::Search => &[Type::Value, Type::Value],
+		}
+	}
+
+	fn result(self) -> Type {
+		match self {
+			FunctionKind::Length | FunctionKind::Count | FunctionKind::Value => Type::Value,
+			FunctionKind::Match | FunctionKind::Search => Type::Logical,
+		}
+	}
+}
+
+/// Anything that can appear on either side of a comparison or as a function argument, before it's type checked
+enum Operand {
+	Constant(Constant),
+	Query(Query),
+	Function(Function),
+}
+
+/// Recursive descent parser for the grammar in RFC 9535
+pub(super) struct Parser<'a> {
+	source: &'a str,
+	pos: usize,
+}
+
+impl<'a> Parser<'a> {
+	pub fn parse(source: &'a str) -> Result<Query, PathError> {
+		let mut parser = Parser { source, pos: 0 };
+
+		parser.expect('$')?;
+		let segments = parser.segments()?;
+
+		match parser.peek() {
+			None => Ok(Query { is_root: true, segments }),
+			Some(_) => Err(parser.unexpected()),
+		}
+	}
+
+	fn peek(&self) -> Option<char> {
+		self.source[self.pos..].chars().next()
+	}
+
+	fn bump(&mut self) -> Option<char> {
+		let current = self.peek()?;
+		self.pos += current.len_utf8();
+		Some(current)
+	}
+
+	fn eat(&mut self, expected: char) -> bool {
+		if self.peek() == Some(expected) {
+			self.pos += expected.len_utf8();
+			true
+		}
+		else {
+			false
+		}
+	}
+
+	fn eat_str(&mut self, expected: &str) -> bool {
+		if self.source[self.pos..].starts_with(expected) {
+			self.pos += expected.len();
+			true
+		}
+		else {
+			false
+		}
+	}
+
+	fn expect(&mut self, expected: char) -> Result<(), PathError> {
+		if self.eat(expected) {
+			Ok(())
+		}
+		else {
+			Err(self.unexpected())
+		}
+	}
+
+	fn skip_whitespace(&mut self) {
+		while let Some(' ') | Some('\t') | Some('\n') | Some('\r') = self.peek() {
+			self.pos += 1;
+		}
+	}
+
+	fn span_from(&self, start: usize) -> Span {
+		Span::new(start, self.pos)
+	}
+
+	fn unexpected(&self) -> PathError {
+		match self.peek() {
+			Some(current) => PathError::UnexpectedCharacter(Span::new(self.pos, self.pos + current.len_utf8()), current),
+			None => PathError::UnexpectedEnd(Span::new(self.pos, self.pos)),
+		}
+	}
+
+	fn segments(&mut self) -> Result<Vec<Segment>, PathError> {
+		let mut segments = Vec::new();
+
+		loop {
+			let start = self.pos;
+			self.skip_whitespace();
+
+			match self.peek() {
+				Some('.') | Some('[') => segments.push(self.segment()?),
+				_ => {
+					// Whitespace belong to whatever come after the query
+					self.pos = start;
+					return Ok(segments);
+				}
+			}
+		}
+	}
+
+	fn segment(&mut self) -> Result<Segment, PathError> {
+		if self.eat_str("..") {
+			let selectors = match self.peek() {
+				Some('[') => self.bracketed()?,
+				Some('*') => {
+					self.bump();
+					vec![Selector::Wildcard]
+				}
+				_ => vec![Selector::Name(self.member_name()?)],
+			};
+			Ok(Segment::Descendant(selectors))
+		}
+		else if self.eat('.') {
+			let selector = if self.eat('*') {
+				Selector::Wildcard
+			}
+			else {
+				Selector::Name(self.member_name()?)
+			};
+			Ok(Segment::Child(vec![selector]))
+		}
+		else {
+			self.bracketed().map(Segment::Child)
+		}
+	}
+
+	fn bracketed(&mut self) -> Result<Vec<Selector>, PathError> {
+		let mut selectors = Vec::new();
+		self.expect('[')?;
+
+		loop {
+			self.skip_whitespace();
+			selectors.push(self.selector()?);
+			self.skip_whitespace();
+
+			if !self.eat(',') {
+				self.expect(']')?;
+				return Ok(selectors);
+			}
+		}
+	}
+
+	fn selector(&mut self) -> Result<Selector, PathError> {
+		match self.peek() {
+			Some('\'') | Some('"') => self.string().map(Selector::Name),
+			Some('*') => {
+				self.bump();
+				Ok(Selector::Wildcard)
+			}
+			Some('?') => {
+				self.bump();
+				self.skip_whitespace();
+				self.logical_or().map(Selector::Filter)
+			}
+			Some('-') | Some(':') | Some('0'..='9') => self.index_or_slice(),
+			_ => Err(self.unexpected()),
+		}
+	}
+
+	fn index_or_slice(&mut self) -> Result<Selector, PathError> {
+		let start = self.optional_int()?;
+		self.skip_whitespace();
+
+		if !self.eat(':') {
+			return start.map(Selector::Index).ok_or_else(|| self.unexpected());
+		}
+
+		self.skip_whitespace();
+		let end = self.optional_int()?;
+		self.skip_whitespace();
+
+		let step = if self.eat(':') {
+			self.skip_whitespace();
+			self.optional_int()?
+		}
+		else {
+			None
+		};
+
+		Ok(Selector::Slice(start, end, step))
+	}
+
+	fn optional_int(&mut self) -> Result<Option<i64>, PathError> {
+		match self.peek() {
+			Some('-') | Some('0'..='9') => self.int().map(Some),
+			_ => Ok(None),
+		}
+	}
+
+	fn int(&mut self) -> Result<i64, PathError> {
+		let start = self.pos;
+		self.eat('-');
+		let digits = self.digits();
+
+		let text = &self.source[start..self.pos];
+		if digits == 0 {
+			return Err(self.unexpected());
+		}
+		if text.starts_with('0') && digits > 1 || text.starts_with("-0") {
+			return Err(PathError::InvalidNumber(self.span_from(start), text.to_owned()));
+		}
+
+		match text.parse::<i64>() {
+			Ok(value) if (-MAX_INT..=MAX_INT).contains(&value) => Ok(value),
+			_ => Err(PathError::InvalidNumber(self.span_from(start), text.to_owned())),
+		}
+	}
+
+	/// Consume ASCII digits and return how many there are
+	fn digits(&mut self) -> usize {
+		let start = self.pos;
+		while let Some('0'..='9') = self.peek() {
+			self.pos += 1;
+		}
+		self.pos - start
+	}
+
+	fn number(&mut self) -> Result<f64, PathError> {
+		let start = self.pos;
+		self.eat('-');
+
+		let int_start = self.pos;
+		let digits = self.digits();
+		if digits == 0 {
+			return Err(self.unexpected());
+		}
+		if digits > 1 && self.source[int_start..].starts_with('0') {
+			return Err(PathError::InvalidNumber(self.span_from(start), self.source[start..self.pos].to_owned()));
+		}
+
+		if self.eat('.') && self.digits() == 0 {
+			return Err(self.unexpected());
+		}
+		if self.eat('e') || self.eat('E') {
+			if !self.eat('+') {
+				self.eat('-');
+			}
+			if self.digits() == 0 {
+				return Err(self.unexpected());
+			}
+		}
+
+		let text = &self.source[start..self.pos];
+		text.parse().map_err(|_| PathError::InvalidNumber(self.span_from(start), text.to_owned()))
+	}
+
+	fn member_name(&mut self) -> Result<String, PathError> {
+		let start = self.pos;
+		let is_first = |x: char| x.is_ascii_alphabetic() || x == '_' || !x.is_ascii();
+
+		match self.peek() {
+			Some(current) if is_first(current) => self.bump(),
+			_ => return Err(self.unexpected()),
+		};
+		while let Some(current) = self.peek() {
+			if !is_first(current) && !current.is_ascii_digit() {
+				break;
+			}
+			self.bump();
+		}
+
+		Ok(self.source[start..self.pos].to_owned())
+	}
+
+	/// Single or double quoted string literal
+	fn string(&mut self) -> Result<String, PathError> {
+		let quote = self.bump().ok_or_else(|| self.unexpected())?;
+		let mut result = String::new();
+
+		loop {
+			let start = self.pos;
+			match self.bump() {
+				None => return Err(self.unexpected()),
+				Some(current) if current == quote => return Ok(result),
+				Some('\\') => result.push(self.escape(start, quote)?),
+				Some(current) if current < ' ' => {
+					self.pos = start;
+					return Err(self.unexpected());
+				}
+				Some(current) => result.push(current),
+			}
+		}
+	}
+
+	fn escape(&mut self, start: usize, quote: char) -> Result<char, PathError> {
+		let escaped = match self.bump() {
+			Some('b') => '\u{8}',
+			Some('f') => '\u{c}',
+			Some('n') => '\n',
+			Some('r') => '\r',
+			Some('t') => '\t',
+			Some('/') => '/',
+			Some('\\') => '\\',
+			Some(current) if current == quote => quote,
+			Some('u') => {
+				let high = self.hex(start)?;
+				if !(0xD800..0xDC00).contains(&high) {
+					return std::char::from_u32(high).ok_or_else(|| PathError::InvalidEscape(self.span_from(start)));
+				}
+
+				if !self.eat_str("\\u") {
+					return Err(PathError::InvalidEscape(self.span_from(start)));
+				}
+				let low = self.hex(start)?;
+				if !(0xDC00..0xE000).contains(&low) {
+					return Err(PathError::InvalidEscape(self.span_from(start)));
+				}
+
+				let code = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+				return std::char::from_u32(code).ok_or_else(|| PathError::InvalidEscape(self.span_from(start)));
+			}
+			_ => return Err(PathError::InvalidEscape(self.span_from(start))),
+		};
+		Ok(escaped)
+	}
+
+	fn hex(&mut self, start: usize) -> Result<u32, PathError> {
+		let digits = self.source.get(self.pos..self.pos + 4).filter(|x| x.chars().all(|x| x.is_ascii_hexdigit()));
+
+		match digits.and_then(|x| u32::from_str_radix(x, 16).ok()) {
+			Some(value) => {
+				self.pos += 4;
+				Ok(value)
+			}
+			None => Err(PathError::InvalidEscape(self.span_from(start))),
+		}
+	}
+
+	fn logical_or(&mut self) -> Result<Logical, PathError> {
+		let mut items = vec![self.logical_and()?];
+
+		loop {
+			let start = self.pos;
+			self.skip_whitespace();
+			if !self.eat_str("||") {
+				self.pos = start;
+				break;
+			}
+			self.skip_whitespace();
+			items.push(self.logical_and()?);
+		}
+
+		Ok(if items.len() == 1 { items.remove(0) } else { Logical::Or(items) })
+	}
+
+	fn logical_and(&mut self) -> Result<Logical, PathError> {
+		let mut items = vec![self.basic()?];
+
+		loop {
+			let start = self.pos;
+			self.skip_whitespace();
+			if !self.eat_str("&&") {
+				self.pos = start;
+				break;
+			}
+			self.skip_whitespace();
+			items.push(self.basic()?);
+		}
+
+		Ok(if items.len() == 1 { items.remove(0) } else { Logical::And(items) })
+	}
+
+	fn basic(&mut self) -> Result<Logical, PathError> {
+		if self.eat('!') {
+			self.skip_whitespace();
+			let inner = if self.peek() == Some('(') { self.paren()? } else { self.test()? };
+			return Ok(Logical::Not(Box::new(inner)));
+		}
+
+		if self.peek() == Some('(') {
+			return self.paren();
+		}
+
+		let start = self.pos;
+		let left = self.operand()?;
+		let left_span = self.span_from(start);
+
+		let end = self.pos;
+		self.skip_whitespace();
+		match self.comparison() {
+			Some(comparison) => {
+				self.skip_whitespace();
+				let start = self.pos;
+				let right = self.operand()?;
+				let right_span = self.span_from(start);
+
+				Ok(Logical::Compare(
+					comparable(left, left_span)?,
+					comparison,
+					comparable(right, right_span)?,
+				))
+			}
+			None => {
+				self.pos = end;
+				test(left, left_span)
+			}
+		}
+	}
+
+	fn paren(&mut self) -> Result<Logical, PathError> {
+		self.expect('(')?;
+		self.skip_whitespace();
+		let inner = self.logical_or()?;
+		self.skip_whitespace();
+		self.expect(')')?;
+		Ok(inner)
+	}
+
+	/// Query or function that is used as a boolean
+	fn test(&mut self) -> Result<Logical, PathError> {
+		let start = self.pos;
+		let operand = self.operand()?;
+		test(operand, self.span_from(start))
+	}
+
+	fn comparison(&mut self) -> Option<Comparison> {
+		let comparison = [
+			("==", Comparison::Equal),
+			("!=", Comparison::NotEqual),
+			("<=", Comparison::LessEqual),
+			(">=", Comparison::GreaterEqual),
+			("<", Comparison::Less),
+			(">", Comparison::Greater),
+		];
+
+		comparison.iter().find(|(symbol, _)| self.eat_str(symbol)).map(|&(_, comparison)| comparison)
+	}
+
+	fn operand(&mut self) -> Result<Operand, PathError> {
+		match self.peek() {
+			Some('@') | Some('$') => {
+				let is_root = self.bump() == Some('$');
+				let segments = self.segments()?;
+				Ok(Operand::Query(Query { is_root, segments }))
+			}
+			Some('\'') | Some('"') => self.string().map(|x| Operand::Constant(Constant::String(x))),
+			Some('-') | Some('0'..='9') => self.number().map(|x| Operand::Constant(Constant::Number(x))),
+			Some('a'..='z') => {
+				let start = self.pos;
+				while let Some('a'..='z') | Some('0'..='9') | Some('_') = self.peek() {
+					self.pos += 1;
+				}
+				let name = &self.source[start..self.pos];
+
+				if self.peek() == Some('(') {
+					return self.function(name, self.span_from(start)).map(Operand::Function);
+				}
+
+				let constant = match name {
+					"true" => Constant::Bool(true),
+					"false" => Constant::Bool(false),
+					"null" => Constant::Null,
+					_ => return Err(self.unexpected()),
+				};
+				Ok(Operand::Constant(constant))
+			}
+			_ => Err(self.unexpected()),
+		}
+	}
+
+	fn function(&mut self, name: &str, name_span: Span) -> Result<Function, PathError> {
+		let kind = FunctionKind::from_name(name).ok_or_else(|| PathError::UnknownFunction(name_span, name.to_owned()))?;
+		let parameters = kind.parameters();
+		let mut args = Vec::new();
+
+		self.expect('(')?;
+		self.skip_whitespace();
+
+		if !self.eat(')') {
+			loop {
+				self.skip_whitespace();
+				let start = self.pos;
+				let arg = self.argument()?;
+				let span = self.span_from(start);
+
+				let parameter = parameters.get(args.len()).ok_or(PathError::ArgumentCount(span, parameters.len()))?;
+				args.push(argument(arg, *parameter, span)?);
+
+				self.skip_whitespace();
+				if !self.eat(',') {
+					self.expect(')')?;
+					break;
+				}
+			}
+		}
+
+		if args.len() != parameters.len() {
+			return Err(PathError::ArgumentCount(Span::new(name_span.start, self.pos), parameters.len()));
+		}
+
+		Ok(Function { kind, args })
+	}
+
+	fn argument(&mut self) -> Result<Argument, PathError> {
+		let start = self.pos;
+		if let Some('!') | Some('(') = self.peek() {
+			return self.logical_or().map(Argument::Logical);
+		}
+
+		let operand = self.operand()?;
+		let end = self.pos;
+		self.skip_whitespace();
+
+		let is_logical = self.comparison().is_some() || self.eat_str("&&") || self.eat_str("||");
+		if is_logical {
+			self.pos = start;
+			return self.logical_or().map(Argument::Logical);
+		}
+
+		self.pos = end;
+		Ok(match operand {
+			Operand::Constant(x) => Argument::Constant(x),
+			Operand::Query(x) => Argument::Query(x),
+			Operand::Function(x) => Argument::Function(x),
+		})
+	}
+}
+
+fn comparable(operand: Operand, span: Span) -> Result<Comparable, PathError> {
+	match operand {
+		Operand::Constant(constant) => Ok(Comparable::Constant(constant)),
+		Operand::Query(query) if query.is_singular() => Ok(Comparable::Query(query)),
+		Operand::Query(_) => Err(PathError::TypeMismatch(span, "singular query")),
+		Operand::Function(function) if function.kind.result() == Type::Value => Ok(Comparable::Function(function)),
+		Operand::Function(_) => Err(PathError::TypeMismatch(span, "function that return a value")),
+	}
+}
+
+fn test(operand: Operand, span: Span) -> Result<Logical, PathError> {
+	match operand {
+		Operand::Query(query) => Ok(Logical::Exists(query)),
+		Operand::Function(function) if function.kind.result() != Type::Value => Ok(Logical::Function(function)),
+		_ => Err(PathError::TypeMismatch(span, "logical expression")),
+	}
+}
+
+/// Check that `arg` is well-typed for `parameter`
+fn argument(arg: Argument, parameter: Type, span: Span) -> Result<Argument, PathError> {
+	let is_valid = match (&arg, parameter) {
+		(Argument::Constant(_), Type::Value) => true,
+		(Argument::Query(query), Type::Value) => query.is_singular(),
+		(Argument::Query(_), _) => true,
+		(Argument::Logical(_), Type::Logical) => true,
+		(Argument::Function(function), Type::Logical) => function.kind.result() != Type::Value,
+		(Argument::Function(function), _) => function.kind.result() == parameter,
+		_ => false,
+	};
+
+	if is_valid {
+		Ok(arg)
+	}
+	else {
+		let expected = match parameter {
+			Type::Value => "value",
+			Type::Logical => "logical expression",
+			Type::Nodes => "query",
+		};
+		Err(PathError::TypeMismatch(span, expected))
+	}
+}
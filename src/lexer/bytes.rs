@@ -0,0 +1,172 @@
+use super::{check_string, continues_number, is_valid_number, ExtendedChar, LexError, Token, BYTE_ORDER_MARK};
+use crate::util::Span;
+use memchr::{memchr, memmem};
+
+/// Byte oriented version of [LexerIter](struct.LexerIter.html) that produce the exact same tokens
+///
+/// ASCII is handled one byte at a time while strings and comments are skipped over with `memchr`,
+/// only non-ASCII characters outside of strings ever get decoded. This is what [Parser](../parser/struct.Parser.html) use.
+///
+/// ```
+/// # use json_peek::lexer::{Lexer, Token};
+/// let content = r#"{ "foo": [1, "bar"] }"#;
+///
+/// assert_eq!(Lexer::new(content).byte_iter().collect::<Vec<_>>(), Lexer::new(content).lex());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ByteLexerIter<'a> {
+	source: &'a str,
+	pos: usize,
+}
+
+impl<'a> ByteLexerIter<'a> {
+	pub const fn new(source: &'a str) -> ByteLexerIter<'a> {
+		ByteLexerIter { source, pos: 0 }
+	}
+
+	fn bytes(&self) -> &'a [u8] {
+		self.source.as_bytes()
+	}
+
+	fn char_at(&self, index: usize) -> char {
+		self.source[index..].chars().next().unwrap_or_default()
+	}
+
+	/// Index of the first character from `index` that doesn't match `predicate`, ASCII is checked without decoding
+	fn scan_while(&self, mut index: usize, mut predicate: impl FnMut(char) -> bool) -> usize {
+		while let Some(&byte) = self.bytes().get(index) {
+			let character = if byte.is_ascii() { byte as char } else { self.char_at(index) };
+			if !predicate(character) {
+				break;
+			}
+			index += character.len_utf8();
+		}
+		index
+	}
+
+	/// Make a token out of `start..end` with `new` and move past it
+	fn token(&mut self, start: usize, end: usize, new: fn(Span, &'a str) -> Token<'a>) -> Token<'a> {
+		self.pos = end;
+		new(Span::new(start, end), self.source)
+	}
+
+	fn error(&mut self, start: usize, end: usize, error: LexError) -> Token<'a> {
+		self.pos = end;
+		Token::new_error(Span::new(start, end), self.source, error)
+	}
+
+	/// Jump from quote to quote until one of them isn't escaped
+	fn lex_string(&mut self, start: usize) -> Token<'a> {
+		let bytes = self.bytes();
+		let mut index = start + 1;
+
+		while let Some(offset) = memchr(b'"', &bytes[index..]) {
+			let quote = index + offset;
+			index = quote + 1;
+
+			let backslashes = bytes[..quote].iter().rev().take_while(|x| **x == b'\\').count();
+			if backslashes % 2 == 0 {
+				self.pos = index;
+				return match check_string(&self.source[start + 1..quote], start + 1) {
+					Some((span, error)) => Token::new_error(span, self.source, error),
+					None => Token::new_string(Span::new(start + 1, quote), self.source),
+				};
+			}
+		}
+
+		self.error(start, bytes.len(), LexError::UnterminatedString)
+	}
+
+	fn lex_comment(&mut self, start: usize) -> Token<'a> {
+		let bytes = self.bytes();
+		match bytes.get(start + 1) {
+			Some(b'/') => {
+				let end = memchr(b'\n', &bytes[start..]).map_or(bytes.len(), |x| start + x);
+				self.token(start, end, Token::new_comment)
+			}
+			Some(b'*') => match memmem::find(&bytes[start + 2..], b"*/") {
+				Some(offset) => self.token(start, start + 2 + offset + 2, Token::new_comment),
+				None => self.error(start, bytes.len(), LexError::UnterminatedComment),
+			},
+			_ => self.error(start, start + 1, LexError::UnexpectedCharacter('/')),
+		}
+	}
+}
+
+impl<'a> Iterator for ByteLexerIter<'a> {
+	type Item = Token<'a>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			let start = self.pos;
+			let token = match *self.bytes().get(start)? {
+				b' ' | b'\t' | b'\n' | b'\r' | b'\x0B' | b'\x0C' => {
+					self.pos += 1;
+					continue;
+				}
+				b'"' => self.lex_string(start),
+				b'{' | b'}' | b'[' | b']' | b',' | b':' => self.token(start, start + 1, Token::new_symbol),
+				b'/' => self.lex_comment(start),
+				_ => {
+					// Same order of checks as `LexerIter`
+					let character = self.char_at(start);
+					let next = start + character.len_utf8();
+					if character.is_number() {
+						let mut previous = character;
+						let end = self.scan_while(next, |x| {
+							let is_number = continues_number(previous, x);
+							previous = x;
+							is_number
+						});
+						if is_valid_number(&self.source[start..end]) {
+							self.token(start, end, Token::new_number)
+						}
+						else {
+							self.error(start, end, LexError::InvalidNumber)
+						}
+					}
+					else if character.is_identifier() {
+						let end = self.scan_while(next, |x| x.is_identifier());
+						self.token(start, end, Token::new_identifier)
+					}
+					else if character.is_whitespace() || (character == BYTE_ORDER_MARK && start == 0) {
+						self.pos = next;
+						continue;
+					}
+					else if character == BYTE_ORDER_MARK {
+						self.error(start, next, LexError::ByteOrderMark)
+					}
+					else {
+						let end = self.scan_while(next, |x| x.is_unexpected());
+						self.error(start, end, LexError::UnexpectedCharacter(character))
+					}
+				}
+			};
+			return Some(token);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::lexer::{Lexer, Token, TokenKind};
+	use crate::util::Span;
+	use proptest::prelude::*;
+
+	fn tokens<'a>(tokens: impl Iterator<Item = Token<'a>>) -> Vec<(Span, TokenKind)> {
+		tokens.map(|x| (x.span, x.kind)).collect()
+	}
+
+	#[test]
+	fn same_as_char_lexer() {
+		let content = "\u{FEFF}{\"a\\\\\": [1, -0.5, 01, tru_e, 1e5, -2.5E+3, 1e+, 2e-1.5, e5], /* c */ \"b\": \"e\u{301}\\\"\\q\", // d\n\"\t\", \u{3000}\u{201C}@, \u{FEFF}é /* e";
+		assert_eq!(tokens(Lexer::new(content).byte_iter()), tokens(Lexer::new(content).into_iter()));
+	}
+
+	proptest! {
+		#[test]
+		fn random_input(content in "[\\[\\]{}:,\"\\\\/*a-zE0-9.+\\- \t\n\u{1}\u{FEFF}\u{3000}\u{201C}é😀]{0,40}") {
+			prop_assert_eq!(tokens(Lexer::new(&content).byte_iter()), tokens(Lexer::new(&content).into_iter()));
+		}
+	}
+}
@@ -0,0 +1,100 @@
+use thiserror::Error;
+
+/// Reason why the lexer couldn't make a valid [Token](struct.Token.html) out of part of the source
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LexError {
+	/// String without the closing quote, the token cover everything until the end of file
+	#[error("Unterminated string")]
+	UnterminatedString,
+
+	/// `/*` without the closing `*/`
+	#[error("Unterminated block comment")]
+	UnterminatedComment,
+
+	/// Unescaped character below `U+0020` inside of a string, the token only cover the character
+	#[error("Control character {} in string", describe(*.0))]
+	ControlCharacter(char),
+
+	/// Backslash that isn't followed by a valid escape, the token cover as much of the escape as possible
+	#[error("Invalid escape sequence")]
+	InvalidEscape,
+
+	/// Number that doesn't follow JSON's grammar like `01`, `1.`, `1.2.3` or `1e+`
+	#[error("Invalid number")]
+	InvalidNumber,
+
+	/// Character that can't start any token, consecutive ones are grouped into a single token and this is the first of them
+	#[error("Unexpected character {}", describe(*.0))]
+	UnexpectedCharacter(char),
+
+	/// `U+FEFF` anywhere other than the very beginning of the source
+	#[error("Byte order mark in the middle of the source")]
+	ByteOrderMark,
+}
+
+/// Code point of `character` along with its Unicode name, or the character itself if it doesn't have one
+fn describe(character: char) -> String {
+	match name(character) {
+		Some(name) => format!("U+{:04X} {}", character as u32, name),
+		None => format!("U+{:04X} `{}`", character as u32, character),
+	}
+}
+
+/// Control characters don't have a name in the Unicode database so their alias is used instead
+fn name(character: char) -> Option<String> {
+	const CONTROL: [&str; 32] = [
+		"NULL",
+		"START OF HEADING",
+		"START OF TEXT",
+		"END OF TEXT",
+		"END OF TRANSMISSION",
+		"ENQUIRY",
+		"ACKNOWLEDGE",
+		"BELL",
+		"BACKSPACE",
+		"CHARACTER TABULATION",
+		"LINE FEED",
+		"LINE TABULATION",
+		"FORM FEED",
+		"CARRIAGE RETURN",
+		"SHIFT OUT",
+		"SHIFT IN",
+		"DATA LINK ESCAPE",
+		"DEVICE CONTROL ONE",
+		"DEVICE CONTROL TWO",
+		"DEVICE CONTROL THREE",
+		"DEVICE CONTROL FOUR",
+		"NEGATIVE ACKNOWLEDGE",
+		"SYNCHRONOUS IDLE",
+		"END OF TRANSMISSION BLOCK",
+		"CANCEL",
+		"END OF MEDIUM",
+		"SUBSTITUTE",
+		"ESCAPE",
+		"INFORMATION SEPARATOR FOUR",
+		"INFORMATION SEPARATOR THREE",
+		"INFORMATION SEPARATOR TWO",
+		"INFORMATION SEPARATOR ONE",
+	];
+
+	match character {
+		'\u{0}'..='\u{1F}' => Some(CONTROL[character as usize].to_owned()),
+		'\u{7F}' => Some("DELETE".to_owned()),
+		'\u{85}' => Some("NEXT LINE".to_owned()),
+		_ => unicode_names2::name(character).map(|name| name.to_string()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn describe_characters() {
+		assert_eq!(LexError::UnexpectedCharacter('\u{201C}').to_string(), "Unexpected character U+201C LEFT DOUBLE QUOTATION MARK");
+		assert_eq!(LexError::UnexpectedCharacter('@').to_string(), "Unexpected character U+0040 COMMERCIAL AT");
+		assert_eq!(LexError::UnexpectedCharacter('\u{2E80}').to_string(), "Unexpected character U+2E80 CJK RADICAL REPEAT");
+		assert_eq!(LexError::UnexpectedCharacter('\u{E000}').to_string(), "Unexpected character U+E000 `\u{E000}`");
+		assert_eq!(LexError::ControlCharacter('\t').to_string(), "Control character U+0009 CHARACTER TABULATION in string");
+	}
+}
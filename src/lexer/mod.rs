@@ -2,7 +2,11 @@ use crate::util::Span;
 use std::iter::Peekable;
 use std::str::CharIndices;
 
+mod bytes;
+mod error;
 mod token;
+pub use bytes::ByteLexerIter;
+pub use error::LexError;
 pub use token::{Token, TokenKind};
 
 /// Shorthand for Lexer to use
@@ -50,6 +54,11 @@ impl<'a> Lexer<'a> {
 		Lexer { source }
 	}
 
+	/// Same as `into_iter()` but with the faster [ByteLexerIter](struct.ByteLexerIter.html)
+	pub const fn byte_iter(self) -> ByteLexerIter<'a> {
+		ByteLexerIter::new(self.source)
+	}
+
 	/// Easily create a `Vec` of [Token](struct.Token.html)
 	pub fn lex(self) -> Vec<Token<'a>> {
 		self.into_iter().collect()
@@ -97,20 +106,41 @@ impl<'a> LexerIter<'a> {
 		self.previous_token() == Some(token)
 	}
 
-	/// Get a `char` before the current token.
+	/// Get the `char` before the last lexed `char`, multi-byte characters are returned whole
 	///
-	/// Can be `None` if this is the beginning of iterator or this is emoji, I really need to make this compatible with UTF-8 /shrug
+	/// Can be `None` if this is the beginning of iterator
 	///
 	/// ```
 	/// # use json_peek::lexer::Lexer;
 	/// let mut lexer = Lexer::new("177013").into_iter();
 	/// lexer.next();
 	/// assert_eq!(lexer.previous_token(), Some('1'));
+	///
+	/// let mut lexer = Lexer::new(r#""é😀""#).into_iter();
+	/// lexer.next();
+	/// assert_eq!(lexer.previous_token(), Some('😀'));
 	/// ```
 	pub fn previous_token(&self) -> Option<char> {
-		let span = self.span.end_point() - Span::new(1, 1);
-		let token = &self.source.get(span.range())?;
-		token.chars().next()
+		let consumed = self.source.get(..self.span.end)?;
+		consumed.chars().rev().nth(1)
+	}
+
+	/// Check if the last lexed `char` is escaped, which is when the current token has an odd number of backslashes right before it
+	///
+	/// ```
+	/// # use json_peek::lexer::Lexer;
+	/// let mut lexer = Lexer::new(r#"\\\""#).into_iter();
+	/// lexer.lex_while(|_, _| true);
+	/// assert!(lexer.is_escaped());
+	///
+	/// let mut lexer = Lexer::new(r#"\\""#).into_iter();
+	/// lexer.lex_while(|_, _| true);
+	/// assert!(!lexer.is_escaped());
+	/// ```
+	pub fn is_escaped(&self) -> bool {
+		let mut consumed = self.value().chars().rev();
+		consumed.next();
+		consumed.take_while(|x| *x == '\\').count() % 2 == 1
 	}
 
 	/// Continue lexing until `predicate` return `false`, will *include* the last item with the result
@@ -119,7 +149,7 @@ impl<'a> LexerIter<'a> {
 	pub fn lex_until(&mut self, predicate: impl Fn(char, &mut LexerIter) -> bool) {
 		while let Some(token) = self.stream.peek() {
 			let &(index, token) = token;
-			self.span.end = index;
+			self.span.end = index + token.len_utf8();
 			self.stream.next();
 
 			if !predicate(token, self) {
@@ -137,17 +167,48 @@ impl<'a> LexerIter<'a> {
 			if !predicate(token, self) {
 				break;
 			}
-			self.span.end = index;
+			self.span.end = index + token.len_utf8();
 			self.stream.next();
 		}
 	}
 
 	/// Lex string literal
 	fn lex_string(&mut self) -> Token<'a> {
-		self.lex_until(|token, iter| {
-			!token.is_quote() || iter.previous_token_is('\\') || iter.span.is_point()
-		});
-		Token::new_string(self.span, self.source).trim(1)
+		// Backslashes can escape each other so a quote is only escaped by an odd number of them
+		self.lex_until(|token, iter| !token.is_quote() || iter.is_escaped());
+
+		// Reaching the end of file without the closing quote
+		let is_closed = self.span.len() > 1 && self.value().ends_with('"') && !self.is_escaped();
+		if !is_closed {
+			return Token::new_error(self.span, self.source, LexError::UnterminatedString);
+		}
+
+		let token = Token::new_string(self.span, self.source).trim(1);
+		match check_string(token.value(), token.span.start) {
+			Some((span, error)) => Token::new_error(span, self.source, error),
+			None => token,
+		}
+	}
+
+	/// Lex `// line` or `/* block */` comment
+	fn lex_comment(&mut self) -> Token<'a> {
+		match self.stream.peek() {
+			Some((_, '/')) => {
+				self.lex_while(|x, _| x != '\n');
+				Token::new_comment(self.span, self.source)
+			}
+			Some((_, '*')) => {
+				self.lex_until(|token, iter| token != '/' || !iter.previous_token_is('*') || iter.span.len() < 4);
+
+				if self.span.len() >= 4 && self.value().ends_with("*/") {
+					Token::new_comment(self.span, self.source)
+				}
+				else {
+					Token::new_error(self.span, self.source, LexError::UnterminatedComment)
+				}
+			}
+			_ => Token::new_error(self.span, self.source, LexError::UnexpectedCharacter('/')),
+		}
 	}
 
 	/// Lex identifier literal
@@ -160,12 +221,24 @@ impl<'a> LexerIter<'a> {
 
 	/// Lex number literal
 	/// 
-	/// Can be represent in regex form as `[\d\-.]+`
+	/// Can be represent in regex form as `[\d\-.]+`, `e` or `E` can also follow a digit and `+` can follow them
 	fn lex_number(&mut self) -> Token<'a> {
 		// NOTE: `is_number()` method will interpret more than `[0-9]` as number
 		// Maybe don't use that?
-		self.lex_while(|x, _| x.is_number());
-		Token::new_number(self.span, self.source)
+		self.lex_while(|x, iter| continues_number(iter.value().chars().next_back().unwrap_or_default(), x));
+
+		if is_valid_number(self.value()) {
+			Token::new_number(self.span, self.source)
+		}
+		else {
+			Token::new_error(self.span, self.source, LexError::InvalidNumber)
+		}
+	}
+
+	/// Lex every consecutive character that can't start a token into a single error
+	fn lex_unexpected(&mut self, first: char) -> Token<'a> {
+		self.lex_while(|x, _| x.is_unexpected());
+		Token::new_error(self.span, self.source, LexError::UnexpectedCharacter(first))
 	}
 }
 
@@ -175,7 +248,7 @@ impl<'a> Iterator for LexerIter<'a> {
 	fn next(&mut self) -> Option<Self::Item> {
 		let (index, token) = self.stream.next()?;
 		self.span.start = index;
-		self.span.end = index;
+		self.span.end = index + token.len_utf8();
 
 		let result = if token.is_quote() {
 			self.lex_string()
@@ -185,10 +258,14 @@ impl<'a> Iterator for LexerIter<'a> {
 			self.lex_identifier()
 		} else if token.is_symbol() {
 			Token::new_symbol(self.span, self.source)
-		} else if token.is_whitespace() {
+		} else if token == '/' {
+			self.lex_comment()
+		} else if token.is_whitespace() || (token == BYTE_ORDER_MARK && index == 0) {
 			self.next()?
+		} else if token == BYTE_ORDER_MARK {
+			Token::new_error(self.span, self.source, LexError::ByteOrderMark)
 		} else {
-			Token::new_unknown(self.span, self.source)
+			self.lex_unexpected(token)
 		};
 
 		Some(result)
@@ -203,11 +280,88 @@ impl<'a> From<&'a str> for LexerIter<'a> {
 	}
 }
 
+const BYTE_ORDER_MARK: char = '\u{FEFF}';
+
+/// Check if `character` is still part of the number that `previous` is the last character of
+///
+/// The exponent is only started right after a digit so `e5` on its own stay an identifier.
+fn continues_number(previous: char, character: char) -> bool {
+	match character {
+		'e' | 'E' => previous.is_ascii_digit(),
+		'+' => previous == 'e' || previous == 'E',
+		_ => character.is_number(),
+	}
+}
+
+/// Check if `value` follow JSON's number grammar, `-?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?`
+fn is_valid_number(value: &str) -> bool {
+	let (digits, exponent) = match value.split_once(['e', 'E']) {
+		Some((digits, exponent)) => (digits, Some(exponent)),
+		None => (value, None),
+	};
+	let digits = digits.strip_prefix('-').unwrap_or(digits);
+	let (integer, fraction) = match digits.split_once('.') {
+		Some((integer, fraction)) => (integer, Some(fraction)),
+		None => (digits, None),
+	};
+	let is_digits = |x: &str| !x.is_empty() && x.bytes().all(|x| x.is_ascii_digit());
+	let is_exponent = |x: &str| is_digits(x.strip_prefix(['+', '-']).unwrap_or(x));
+
+	is_digits(integer)
+		&& (integer == "0" || !integer.starts_with('0'))
+		&& fraction.map(is_digits).unwrap_or(true)
+		&& exponent.map(is_exponent).unwrap_or(true)
+}
+
+/// First problem with the content of a string and where it is, `offset` is where `content` start in the source
+///
+/// Work on bytes since everything it look for is ASCII and never part of a multi-byte character.
+fn check_string(content: &str, offset: usize) -> Option<(Span, LexError)> {
+	let bytes = content.as_bytes();
+	let mut index = 0;
+	while let Some(&byte) = bytes.get(index) {
+		let start = offset + index;
+		if byte == b'\\' {
+			match escape_length(&content[index..]) {
+				Ok(length) => index += length,
+				Err(length) => return Some((Span::new(start, start + length), LexError::InvalidEscape)),
+			}
+		}
+		else if byte < 0x20 {
+			return Some((Span::new(start, start + 1), LexError::ControlCharacter(byte as char)));
+		}
+		else {
+			index += 1;
+		}
+	}
+	None
+}
+
+/// Length of the escape sequence at the start of `text`, `Err` contain the length of the invalid part
+fn escape_length(text: &str) -> Result<usize, usize> {
+	let mut chars = text.chars().skip(1);
+	match chars.next() {
+		Some('"' | '\\' | '/' | 'b' | 'f' | 'n' | 'r' | 't') => Ok(2),
+		Some('u') => {
+			let hex = chars.take(4).take_while(char::is_ascii_hexdigit).count();
+			if hex == 4 {
+				Ok(6)
+			}
+			else {
+				Err(2 + hex)
+			}
+		}
+		Some(character) => Err(1 + character.len_utf8()),
+		None => Err(1),
+	}
+}
+
 trait ExtendedChar {
 	fn is_number(&self) -> bool;
 	fn is_symbol(&self) -> bool;
 	fn is_quote(&self) -> bool;
 	fn is_identifier(&self) -> bool;
+	fn is_unexpected(&self) -> bool;
 }
 
 impl ExtendedChar for char {
@@ -232,6 +386,15 @@ impl ExtendedChar for char {
 	fn is_identifier(&self) -> bool {
 		self.is_alphanumeric() || *self == '_'
 	}
+
+	fn is_unexpected(&self) -> bool {
+		!(self.is_number()
+			|| self.is_identifier()
+			|| self.is_symbol()
+			|| self.is_whitespace()
+			|| *self == '/'
+			|| *self == BYTE_ORDER_MARK)
+	}
 }
 
 #[cfg(test)]
@@ -374,4 +537,109 @@ mod tests {
 			Token::test_symbol("}"),
 		]);
 	}
+
+	#[test]
+	fn comments() {
+		let content = "[1, // one\n/* two */ 2 /*/ 3] /**/ /* three";
+		let tokens = Lexer::new(content).lex();
+
+		assert_eq!(tokens, vec![
+			Token::test_symbol("["),
+			Token::test_number("1"),
+			Token::test_symbol(","),
+			Token::test_comment("// one"),
+			Token::test_comment("/* two */"),
+			Token::test_number("2"),
+			Token::test_comment("/*/ 3] /**/"),
+			Token::test_error("/* three", LexError::UnterminatedComment),
+		]);
+	}
+
+	#[test]
+	fn unterminated_string() {
+		let mut lexer = Lexer::new(r#"["foo"#).into_iter();
+
+		assert_eq!(lexer.next(), Token::test_symbol("[").into());
+		assert_eq!(lexer.next(), Token::test_error(r#""foo"#, LexError::UnterminatedString).into());
+		assert_eq!(lexer.next(), None);
+	}
+
+	#[test]
+	fn lex_errors() {
+		let content = "[\u{FEFF}01, 1.5, -, \"a\\qb\", \"\\u12x\", \"c\td\", @#\u{201C}, true, 1e+, 2E-1.5]";
+		let errors: Vec<_> = Lexer::new(content).lex().into_iter().filter_map(|x| match x.kind {
+			TokenKind::Error(error) => Some((x.span, error)),
+			_ => None,
+		}).collect();
+
+		assert_eq!(errors, vec![
+			(Span::new(1, 4), LexError::ByteOrderMark),
+			(Span::new(4, 6), LexError::InvalidNumber),
+			(Span::new(13, 14), LexError::InvalidNumber),
+			(Span::new(18, 20), LexError::InvalidEscape),
+			(Span::new(25, 29), LexError::InvalidEscape),
+			(Span::new(35, 36), LexError::ControlCharacter('\t')),
+			(Span::new(40, 45), LexError::UnexpectedCharacter('@')),
+			(Span::new(53, 56), LexError::InvalidNumber),
+			(Span::new(58, 64), LexError::InvalidNumber),
+		]);
+		assert_eq!(Lexer::new("\u{FEFF}[]").lex(), vec![Token::test_symbol("["), Token::test_symbol("]")]);
+	}
+
+	#[test]
+	fn exponents() {
+		let tokens = Lexer::new("[1e5, -2.5E+3, 0e-0, e5]").lex();
+
+		assert_eq!(tokens, vec![
+			Token::test_symbol("["),
+			Token::test_number("1e5"),
+			Token::test_symbol(","),
+			Token::test_number("-2.5E+3"),
+			Token::test_symbol(","),
+			Token::test_number("0e-0"),
+			Token::test_symbol(","),
+			Token::test_identifier("e5"),
+			Token::test_symbol("]"),
+		]);
+	}
+
+	#[test]
+	fn escaped_backslashes() {
+		let content = r#"["a\\", "b\\\"c", "\\\\", "d\""#;
+		let tokens = Lexer::new(content).lex();
+
+		assert_eq!(tokens, vec![
+			Token::test_symbol("["),
+			Token::test_string(r#"a\\"#),
+			Token::test_symbol(","),
+			Token::test_string(r#"b\\\"c"#),
+			Token::test_symbol(","),
+			Token::test_string(r#"\\\\"#),
+			Token::test_symbol(","),
+			Token::test_error(r#""d\""#, LexError::UnterminatedString),
+		]);
+	}
+
+	#[test]
+	fn non_ascii_strings() {
+		// CJK, emoji with a zero width joiner, and `e` followed by a combining acute accent
+		let content = "{\"名前\": \"魔法使い\\\"めぐみん\\\"\", \"👩\u{200D}🔬\": \"e\u{301}\\\\\"}";
+		let tokens = Lexer::new(content).lex();
+
+		assert_eq!(tokens, vec![
+			Token::test_symbol("{"),
+			Token::test_string("名前"),
+			Token::test_symbol(":"),
+			Token::test_string("魔法使い\\\"めぐみん\\\""),
+			Token::test_symbol(","),
+			Token::test_string("👩\u{200D}🔬"),
+			Token::test_symbol(":"),
+			Token::test_string("e\u{301}\\\\"),
+			Token::test_symbol("}"),
+		]);
+
+		let spans: Vec<_> = tokens.iter().map(|x| &content[x.full_span().range()]).collect();
+		assert_eq!(spans[3], "\"魔法使い\\\"めぐみん\\\"\"");
+		assert_eq!(spans[7], "\"e\u{301}\\\\\"");
+	}
 }
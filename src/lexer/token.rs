@@ -1,3 +1,4 @@
+use super::LexError;
 use crate::util::Span;
 use std::fmt;
 use std::str::FromStr;
@@ -5,6 +6,8 @@ use std::str::FromStr;
 /// A single point of string that represent a useful symbol for [Parser](../parser/struct.Parser.html) to use
 #[derive(Clone, Copy, Eq)]
 pub struct Token<'a> {
+	/// Position of the token's value, for [String](enum.TokenKind.html#variant.String) token this *doesn't* include the quotes.
+	/// See [full_span()](#method.full_span) for that.
 	pub span: Span,
 	source: &'a str,
 	pub kind: TokenKind,
@@ -31,8 +34,12 @@ impl<'a> Token<'a> {
 		Token::new(span, source, TokenKind::Identifier)
 	}
 
-	pub const fn new_unknown(span: Span, source: &'a str) -> Token<'a> {
-		Token::new(span, source, TokenKind::Unknown)
+	pub const fn new_comment(span: Span, source: &'a str) -> Token<'a> {
+		Token::new(span, source, TokenKind::Comment)
+	}
+
+	pub const fn new_error(span: Span, source: &'a str, error: LexError) -> Token<'a> {
+		Token::new(span, source, TokenKind::Error(error))
 	}
 
 	/// Get a string reprsentation of what `Token` is pointing to
@@ -40,6 +47,36 @@ impl<'a> Token<'a> {
 		&self.source.get(self.span.range()).unwrap_or_default()
 	}
 
+	/// Position of the whole token as it appear in the source, including the quotes of a string
+	///
+	/// ```
+	/// # use json_peek::lexer::Lexer;
+	/// # use json_peek::util::Span;
+	/// let token = Lexer::new(r#"  "foo""#).lex()[0];
+	///
+	/// assert_eq!(token.span, Span::new(3, 6));
+	/// assert_eq!(token.full_span(), Span::new(2, 7));
+	/// ```
+	pub fn full_span(&self) -> Span {
+		match self.kind {
+			TokenKind::String => Span::new(self.span.start.saturating_sub(1), self.span.end + 1),
+			_ => self.span,
+		}
+	}
+
+	/// Check if this is a [Symbol](enum.TokenKind.html#variant.Symbol) token of `symbol`
+	///
+	/// Unlike comparing with `char` directly, this won't match a string that happen to start with `symbol`
+	///
+	/// ```
+	/// # use json_peek::lexer::Token;
+	/// assert!(Token::test_symbol("{").is_symbol('{'));
+	/// assert!(!Token::test_string("{").is_symbol('{'));
+	/// ```
+	pub fn is_symbol(&self, symbol: char) -> bool {
+		self.kind == TokenKind::Symbol && *self == symbol
+	}
+
 	/// See [Span::trim()](../util/struct.Span.html#method.trim)
 	/// 
 	/// ```
@@ -61,7 +98,7 @@ impl<'a> Token<'a> {
 
 	/// Create "test" Token which is use inside a unit test to easily create a mock Token
 	pub const fn test(value: &'a str, kind: TokenKind) -> Token<'a> {
-		let span = Span::new(0, value.len().saturating_sub(1));
+		let span = Span::new(0, value.len());
 		Token::new(span, value, kind)
 	}
 
@@ -81,8 +118,12 @@ impl<'a> Token<'a> {
 		Token::test(value, TokenKind::Identifier)
 	}
 
-	pub const fn test_unknown(value: &'a str) -> Token<'a> {
-		Token::test(value, TokenKind::Unknown)
+	pub const fn test_comment(value: &'a str) -> Token<'a> {
+		Token::test(value, TokenKind::Comment)
+	}
+
+	pub const fn test_error(value: &'a str, error: LexError) -> Token<'a> {
+		Token::test(value, TokenKind::Error(error))
 	}
 }
 
@@ -134,6 +175,8 @@ pub enum TokenKind {
 	Symbol,
 	/// Token is made up of numbers, alphabet and underscore (`_`) but isn't surrounded by quote ('"')
 	Identifier,
-	/// Lexer wasn't able to determine the type of this Token
-	Unknown,
+	/// Token is a `// line` or `/* block */` comment including its delimiters, JSON doesn't allow them so [Parser](../parser/struct.Parser.html) reject it
+	Comment,
+	/// Lexer wasn't able to make a valid Token out of this part of the source
+	Error(LexError),
 }
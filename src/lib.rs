@@ -38,14 +38,25 @@
 //! assert_eq!(title, "Installed Datapacks");
 //! ```
 
+/// Source formatter that reindent JSON and JSONC text while keeping comments
+pub mod format;
+/// JSONPath queries that return the span of every match
+pub mod jsonpath;
+/// Pluggable lint rules that report spanned diagnostics
+pub mod lint;
 /// Lexer module which parse string into usable [Token](lexer/struct.Token.html)
 pub mod lexer;
+/// Minecraft specific interpretation of JSON value
+pub mod minecraft;
 /// Parser module which handling interpreting [Token](lexer/struct.Token.html) into JSON AST
 pub mod parser;
+/// JSON Pointer, structural diff and JSON Patch
+pub mod patch;
 /// Utility module
 pub mod util;
 pub mod value;
 
-pub use value::{Value, Indexable, Keyable};
+pub use value::{Value, Indexable, Keyable, FromValue};
+pub use json_peek_derive::FromValue;
 pub use parser::Parser;
 pub use lexer::{Lexer, Token};
\ No newline at end of file
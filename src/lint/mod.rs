@@ -0,0 +1,247 @@
+use crate::patch::JsonPointer;
+use crate::util::Span;
+use crate::value::Value;
+use std::collections::HashMap;
+use std::fmt;
+
+mod rules;
+
+pub use rules::{DuplicateKeys, EmptyString, TrailingWhitespace};
+
+/// How serious a [Diagnostic](struct.Diagnostic.html) is, `Allow` disable the rule completely
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+	Allow,
+	Warn,
+	Deny,
+}
+
+impl fmt::Display for Severity {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Severity::Allow => write!(f, "allow"),
+			Severity::Warn => write!(f, "warning"),
+			Severity::Deny => write!(f, "error"),
+		}
+	}
+}
+
+/// Problem reported by a [Rule](trait.Rule.html)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+	/// Name of the rule that report this
+	pub rule: &'static str,
+	pub severity: Severity,
+	pub span: Span,
+	/// Location of the node inside of the tree
+	pub path: JsonPointer,
+	pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}[{}]: {} at {}", self.severity, self.rule, self.message, self.span)
+	}
+}
+
+/// Single check that [Linter](struct.Linter.html) run, every hook does nothing by default
+pub trait Rule {
+	/// Unique name of this rule, used to change its severity or suppress it
+	fn name(&self) -> &'static str;
+
+	fn default_severity(&self) -> Severity {
+		Severity::Warn
+	}
+
+	/// Called with every node in the tree, parents come before their children
+	fn check_value(&self, _value: &Value, _context: &mut Context) {}
+
+	/// Called once with the source that the tree is parsed from, for problems that doesn't survive parsing
+	fn check_source(&self, _source: &str, _context: &mut Context) {}
+}
+
+/// Where a [Rule](trait.Rule.html) report its diagnostics
+pub struct Context {
+	rule: &'static str,
+	severity: Severity,
+	path: JsonPointer,
+	diagnostics: Vec<Diagnostic>,
+}
+
+impl Context {
+	/// Path of the node that is being checked, this is the root inside of [Rule::check_source()](trait.Rule.html#method.check_source)
+	pub fn path(&self) -> &JsonPointer {
+		&self.path
+	}
+
+	pub fn report(&mut self, span: Span, message: impl Into<String>) {
+		let path = self.path.clone();
+		self.report_at(path, span, message);
+	}
+
+	/// Report a problem at `path` instead of the current node
+	pub fn report_at(&mut self, path: JsonPointer, span: Span, message: impl Into<String>) {
+		self.diagnostics.push(Diagnostic {
+			rule: self.rule,
+			severity: self.severity,
+			span,
+			path,
+			message: message.into(),
+		});
+	}
+}
+
+/// Registry of [Rule](trait.Rule.html)s along with their severities and suppressions
+///
+/// ```
+/// # use json_peek::util;
+/// # use json_peek::lint::{Linter, Severity};
+/// # use json_peek::util::Span;
+/// let content = r#"{ "title": "Hello ", "title": "", "tags": ["", "a "] }"#;
+/// let value = util::from_str(content).unwrap();
+///
+/// let mut linter = Linter::default();
+/// linter.set_severity("empty-string", Severity::Deny);
+/// linter.suppress("trailing-whitespace", "/tags".parse().unwrap());
+///
+/// let diagnostics = linter.run(&value, content);
+/// let rules: Vec<_> = diagnostics.iter().map(|x| (x.rule, x.severity, x.span)).collect();
+/// assert_eq!(rules, [
+///     ("duplicate-keys", Severity::Deny, Span::new(21, 28)),
+///     ("empty-string", Severity::Deny, Span::new(30, 32)),
+///     ("empty-string", Severity::Deny, Span::new(43, 45)),
+/// ]);
+/// ```
+pub struct Linter {
+	rules: Vec<Box<dyn Rule>>,
+	severities: HashMap<&'static str, Severity>,
+	suppressions: Vec<(String, JsonPointer)>,
+}
+
+impl Linter {
+	/// Linter without any rule
+	pub fn new() -> Linter {
+		Linter {
+			rules: Vec::new(),
+			severities: HashMap::new(),
+			suppressions: Vec::new(),
+		}
+	}
+
+	/// Linter with every built-in rule
+	pub fn with_builtin_rules() -> Linter {
+		let mut linter = Linter::new();
+		linter.add_rule(DuplicateKeys);
+		linter.add_rule(EmptyString);
+		linter.add_rule(TrailingWhitespace);
+		linter
+	}
+
+	pub fn add_rule(&mut self, rule: impl Rule + 'static) {
+		self.severities.insert(rule.name(), rule.default_severity());
+		self.rules.push(Box::new(rule));
+	}
+
+	/// Change the severity of the rule named `rule`, does nothing if there is no such rule
+	pub fn set_severity(&mut self, rule: &str, severity: Severity) {
+		if let Some((_, current)) = self.severities.iter_mut().find(|(name, _)| **name == rule) {
+			*current = severity;
+		}
+	}
+
+	pub fn severity(&self, rule: &str) -> Option<Severity> {
+		self.severities.get(rule).copied()
+	}
+
+	/// Ignore everything that `rule` report at `path` or inside of it
+	pub fn suppress(&mut self, rule: &str, path: JsonPointer) {
+		self.suppressions.push((rule.to_owned(), path));
+	}
+
+	/// Run every rule that isn't allowed over `value` which is parsed from `source`, diagnostics are sorted by their position
+	pub fn run(&self, value: &Value, source: &str) -> Vec<Diagnostic> {
+		let mut context = Context {
+			rule: "",
+			severity: Severity::Allow,
+			path: JsonPointer::root(),
+			diagnostics: Vec::new(),
+		};
+
+		let rules: Vec<_> = self
+			.rules
+			.iter()
+			.map(|rule| (rule, self.severities[rule.name()]))
+			.filter(|(_, severity)| *severity != Severity::Allow)
+			.collect();
+
+		for (rule, severity) in &rules {
+			context.rule = rule.name();
+			context.severity = *severity;
+			rule.check_source(source, &mut context);
+		}
+
+		for (path, value) in value.depth_first() {
+			context.path = path;
+			for (rule, severity) in &rules {
+				context.rule = rule.name();
+				context.severity = *severity;
+				rule.check_value(value, &mut context);
+			}
+		}
+
+		let mut diagnostics: Vec<_> = context.diagnostics.into_iter().filter(|x| !self.is_suppressed(x)).collect();
+		diagnostics.sort_by_key(|x| x.span.start);
+		diagnostics
+	}
+
+	fn is_suppressed(&self, diagnostic: &Diagnostic) -> bool {
+		self.suppressions
+			.iter()
+			.any(|(rule, path)| rule == diagnostic.rule && diagnostic.path.tokens().starts_with(path.tokens()))
+	}
+}
+
+/// Same as [with_builtin_rules()](#method.with_builtin_rules)
+impl Default for Linter {
+	fn default() -> Linter {
+		Linter::with_builtin_rules()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::util;
+
+	struct DeepNesting(usize);
+
+	impl Rule for DeepNesting {
+		fn name(&self) -> &'static str {
+			"deep-nesting"
+		}
+
+		fn check_value(&self, value: &Value, context: &mut Context) {
+			if context.path().tokens().len() > self.0 && (value.is_object() || value.is_array()) {
+				context.report(value.span(), "too deep");
+			}
+		}
+	}
+
+	#[test]
+	fn custom_rule() {
+		let content = r#"{ "a": [[1], {"b": {}}] }"#;
+		let value = util::from_str(content).unwrap();
+
+		let mut linter = Linter::new();
+		linter.add_rule(DeepNesting(1));
+
+		let paths: Vec<_> = linter.run(&value, content).iter().map(|x| x.path.to_string()).collect();
+		assert_eq!(paths, ["/a/0", "/a/1", "/a/1/b"]);
+
+		linter.suppress("deep-nesting", "/a/1".parse().unwrap());
+		assert_eq!(linter.run(&value, content).len(), 1);
+
+		linter.set_severity("deep-nesting", Severity::Allow);
+		assert!(linter.run(&value, content).is_empty());
+	}
+}
@@ -0,0 +1,152 @@
+use super::{Context, Rule, Severity};
+use crate::lexer::{Lexer, TokenKind};
+use crate::patch::JsonPointer;
+use crate::util::{unescape, Span};
+use crate::value::Value;
+use std::collections::HashMap;
+
+/// Object that define the same key more than once, only the last value is kept by the parser
+/// so this rule look at the source instead of the tree.
+pub struct DuplicateKeys;
+
+/// Object or array that the lexer is currently inside of
+enum Frame {
+	Object {
+		keys: HashMap<String, Span>,
+		key: Option<String>,
+		expect_key: bool,
+	},
+	Array(usize),
+}
+
+impl Frame {
+	/// Path token of the value that is currently being lexed inside of this frame
+	fn token(&self) -> Option<String> {
+		match self {
+			Frame::Object { key, .. } => key.clone(),
+			Frame::Array(index) => Some(index.to_string()),
+		}
+	}
+}
+
+impl Rule for DuplicateKeys {
+	fn name(&self) -> &'static str {
+		"duplicate-keys"
+	}
+
+	fn default_severity(&self) -> Severity {
+		Severity::Deny
+	}
+
+	fn check_source(&self, source: &str, context: &mut Context) {
+		let mut stack: Vec<Frame> = Vec::new();
+
+		for token in Lexer::new(source) {
+			match token.kind {
+				TokenKind::Symbol if token.is_symbol('{') => stack.push(Frame::Object {
+					keys: HashMap::new(),
+					key: None,
+					expect_key: true,
+				}),
+				TokenKind::Symbol if token.is_symbol('[') => stack.push(Frame::Array(0)),
+				TokenKind::Symbol if token.is_symbol('}') || token.is_symbol(']') => {
+					stack.pop();
+				}
+				TokenKind::Symbol if token.is_symbol(',') => match stack.last_mut() {
+					Some(Frame::Object { expect_key, .. }) => *expect_key = true,
+					Some(Frame::Array(index)) => *index += 1,
+					None => {}
+				},
+				TokenKind::String | TokenKind::Identifier => {
+					let parents: Vec<_> = stack[..stack.len().saturating_sub(1)].iter().filter_map(Frame::token).collect();
+					if let Some(Frame::Object { keys, key, expect_key }) = stack.last_mut() {
+						if !*expect_key {
+							continue;
+						}
+						*expect_key = false;
+
+						let name = unescape(token.value()).into_owned();
+						*key = Some(name.clone());
+						match keys.get(&name) {
+							Some(first) => {
+								let mut path = JsonPointer::root();
+								for parent in parents {
+									path.push(parent);
+								}
+								path.push(name.as_str());
+								let message = format!("Duplicate key {:?}, first defined at {}", name, first);
+								context.report_at(path, token.full_span(), message);
+							}
+							None => {
+								keys.insert(name, token.full_span());
+							}
+						}
+					}
+				}
+				_ => {}
+			}
+		}
+	}
+}
+
+/// String without any character, `""`
+pub struct EmptyString;
+
+impl Rule for EmptyString {
+	fn name(&self) -> &'static str {
+		"empty-string"
+	}
+
+	fn check_value(&self, value: &Value, context: &mut Context) {
+		if let Value::Literal(literal) = value {
+			if literal.value.is_empty() {
+				context.report(literal.span, "Empty string");
+			}
+		}
+	}
+}
+
+/// String that start or end with whitespace after unescaping
+pub struct TrailingWhitespace;
+
+impl Rule for TrailingWhitespace {
+	fn name(&self) -> &'static str {
+		"trailing-whitespace"
+	}
+
+	fn check_value(&self, value: &Value, context: &mut Context) {
+		if let Value::Literal(literal) = value {
+			let text = unescape(&literal.value);
+			if text.trim() != text && !text.trim().is_empty() {
+				context.report(literal.span, "String has leading or trailing whitespace");
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::lint::{DuplicateKeys, Linter};
+	use crate::util::{self, Span};
+
+	#[test]
+	fn nested_duplicate_keys() {
+		let content = r#"{ "a": [{ "b": 1, "c": [], "b": 2 }], "a": null, "d": { "b": 3 } }"#;
+		let value = util::from_str(content).unwrap();
+
+		let mut linter = Linter::new();
+		linter.add_rule(DuplicateKeys);
+
+		let diagnostics: Vec<_> = linter.run(&value, content).into_iter().map(|x| (x.path.to_string(), x.span)).collect();
+		assert_eq!(diagnostics, [("/a/0/b".to_owned(), Span::new(27, 30)), ("/a".to_owned(), Span::new(38, 41))]);
+	}
+
+	#[test]
+	fn whitespace() {
+		let content = r#"[" a", "b\t", "   ", "c d"]"#;
+		let value = util::from_str(content).unwrap();
+
+		let spans: Vec<_> = Linter::default().run(&value, content).into_iter().map(|x| x.span).collect();
+		assert_eq!(spans, [Span::new(1, 5), Span::new(7, 12)]);
+	}
+}
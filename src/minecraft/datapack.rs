@@ -0,0 +1,646 @@
+use super::text::{TextComponent, TextError};
+use super::{ResourceLocation, ResourceLocationError, DEFAULT_NAMESPACE};
+use crate::util::Span;
+use crate::value::{Array, Literal, Object, Value};
+use std::path::Path;
+use thiserror::Error;
+
+/// Kind of JSON file that can be found inside of a datapack
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileKind {
+	Advancement,
+	Recipe,
+	LootTable,
+	Predicate,
+	ItemModifier,
+	Tag,
+	PackMcmeta,
+}
+
+impl FileKind {
+	/// Guess the kind of file from its path inside of a datapack
+	///
+	/// ```
+	/// # use json_peek::minecraft::datapack::FileKind;
+	/// assert_eq!(FileKind::from_path("pack.mcmeta"), Some(FileKind::PackMcmeta));
+	/// assert_eq!(FileKind::from_path("data/foo/advancements/bar.json"), Some(FileKind::Advancement));
+	/// assert_eq!(FileKind::from_path("data/minecraft/tags/blocks/logs.json"), Some(FileKind::Tag));
+	/// assert_eq!(FileKind::from_path("data/foo/functions/bar.mcfunction"), None);
+	/// ```
+	pub fn from_path(path: impl AsRef<Path>) -> Option<FileKind> {
+		let path = path.as_ref();
+		if path.file_name()? == "pack.mcmeta" {
+			return Some(FileKind::PackMcmeta);
+		}
+		if path.extension()? != "json" {
+			return None;
+		}
+
+		// data/<namespace>/<kind>/...
+		let mut components = path.iter().skip_while(|&x| x != "data").skip(2);
+		let kind = match components.next()?.to_str()? {
+			"advancements" => FileKind::Advancement,
+			"recipes" => FileKind::Recipe,
+			"loot_tables" => FileKind::LootTable,
+			"predicates" => FileKind::Predicate,
+			"item_modifiers" => FileKind::ItemModifier,
+			"tags" => FileKind::Tag,
+			_ => return None,
+		};
+		Some(kind)
+	}
+}
+
+/// Problem found while validating a datapack file
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+pub enum DatapackError {
+	/// Value has the wrong type, contain what type was expected and what was found
+	#[error("Expected {1} but found {2} at {0}")]
+	InvalidType(Span, &'static str, &'static str),
+
+	/// Object is missing a required key
+	#[error("Missing key {1:?} at {0}")]
+	MissingKey(Span, &'static str),
+
+	/// String isn't a valid `namespace:path` or it's a tag where tag isn't allowed
+	#[error(transparent)]
+	ResourceLocation(#[from] ResourceLocationError),
+
+	/// Value has the right type but isn't one of the allowed values
+	#[error("Invalid value {1:?} at {0}")]
+	InvalidValue(Span, String),
+
+	/// Array that need at least one item is empty
+	#[error("Expected at least one item at {0}")]
+	Empty(Span),
+
+	/// Problem inside of a text component
+	#[error(transparent)]
+	Text(#[from] TextError),
+}
+
+/// Validate `value` as the given kind of datapack file, return every problem found
+///
+/// ```
+/// # use json_peek::util;
+/// # use json_peek::util::Span;
+/// # use json_peek::minecraft::ResourceLocationError;
+/// # use json_peek::minecraft::datapack::{self, DatapackError, FileKind};
+/// let content = r##"{ "values": ["minecraft:oak_log", "#minecraft:Logs"] }"##;
+/// let value = util::from_str(content).unwrap();
+///
+/// assert_eq!(
+///     datapack::validate(FileKind::Tag, &value),
+///     Err(vec![DatapackError::ResourceLocation(ResourceLocationError::InvalidPath(Span::new(46, 50), "Logs".to_owned()))])
+/// );
+/// ```
+pub fn validate(kind: FileKind, value: &Value) -> Result<(), Vec<DatapackError>> {
+	let mut checker = Checker::default();
+
+	match kind {
+		FileKind::Advancement => checker.advancement(value),
+		FileKind::Recipe => checker.recipe(value),
+		FileKind::LootTable => checker.loot_table(value),
+		FileKind::Predicate => checker.one_or_many(value, Checker::condition),
+		FileKind::ItemModifier => checker.one_or_many(value, Checker::function),
+		FileKind::Tag => checker.tag(value),
+		FileKind::PackMcmeta => checker.pack_mcmeta(value),
+	}
+
+	if checker.errors.is_empty() {
+		Ok(())
+	}
+	else {
+		Err(checker.errors)
+	}
+}
+
+#[derive(Debug, Default)]
+struct Checker {
+	errors: Vec<DatapackError>,
+}
+
+impl Checker {
+	fn invalid_type(&mut self, value: &Value, expected: &'static str) {
+		self.errors.push(DatapackError::InvalidType(value.span(), expected, value.kind_name()));
+	}
+
+	fn object<'a>(&mut self, value: &'a Value) -> Option<&'a Object> {
+		match value {
+			Value::Object(item) => Some(item),
+			_ => {
+				self.invalid_type(value, "object");
+				None
+			}
+		}
+	}
+
+	fn array<'a>(&mut self, value: &'a Value) -> Option<&'a Array> {
+		match value {
+			Value::Array(item) => Some(item),
+			_ => {
+				self.invalid_type(value, "array");
+				None
+			}
+		}
+	}
+
+	fn string(&mut self, value: &Value) -> Option<Literal> {
+		match value {
+			Value::Literal(item) => Some(item.clone()),
+			_ => {
+				self.invalid_type(value, "string");
+				None
+			}
+		}
+	}
+
+	fn number(&mut self, value: &Value) {
+		if !value.is_number() {
+			self.invalid_type(value, "number");
+		}
+	}
+
+	fn boolean(&mut self, value: &Value) {
+		if !value.is_bool() {
+			self.invalid_type(value, "boolean");
+		}
+	}
+
+	fn resource_location(&mut self, value: &Value, allow_tag: bool) -> Option<ResourceLocation> {
+		let item = self.string(value)?;
+
+		match ResourceLocation::from_literal(&item) {
+			Ok(location) if location.is_tag() && !allow_tag => {
+				self.errors.push(ResourceLocationError::UnexpectedTag(location.span()).into());
+				None
+			}
+			Ok(location) => Some(location),
+			Err(error) => {
+				self.errors.push(error.into());
+				None
+			}
+		}
+	}
+
+	fn one_of(&mut self, value: &Value, allowed: &[&str]) {
+		if let Some(item) = self.string(value) {
+			if !allowed.contains(&item.value.as_str()) {
+				self.errors.push(DatapackError::InvalidValue(item.span, item.value));
+			}
+		}
+	}
+
+	fn text(&mut self, value: &Value) {
+		if let Err(errors) = TextComponent::from_value(value) {
+			self.errors.extend(errors.into_iter().map(DatapackError::from));
+		}
+	}
+
+	fn required<'a>(&mut self, object: &'a Object, key: &'static str) -> Option<&'a Value> {
+		let value = object.value.get(&Literal::new_literal(key));
+		if value.is_none() {
+			self.errors.push(DatapackError::MissingKey(object.span, key));
+		}
+		value
+	}
+
+	fn optional<'a>(&mut self, object: &'a Object, key: &str) -> Option<&'a Value> {
+		object.value.get(&Literal::new_literal(key))
+	}
+
+	/// Run `check` on every item of an array
+	fn each(&mut self, value: &Value, check: impl Fn(&mut Checker, &Value)) {
+		if let Some(array) = self.array(value) {
+			for value in array.value.iter() {
+				check(self, value);
+			}
+		}
+	}
+
+	/// Some file can be either a single object or an array of them
+	fn one_or_many(&mut self, value: &Value, check: fn(&mut Checker, &Value)) {
+		match value {
+			Value::Array(array) => array.value.iter().for_each(|x| check(self, x)),
+			_ => check(self, value),
+		}
+	}
+
+	fn advancement(&mut self, value: &Value) {
+		let root = match self.object(value) {
+			Some(root) => root,
+			None => return,
+		};
+
+		if let Some(parent) = self.optional(root, "parent") {
+			self.resource_location(parent, false);
+		}
+
+		if let Some(criteria) = self.required(root, "criteria").and_then(|x| self.object(x)) {
+			if criteria.value.is_empty() {
+				self.errors.push(DatapackError::Empty(criteria.span));
+			}
+			for criterion in criteria.value.values() {
+				if let Some(criterion) = self.object(criterion) {
+					if let Some(trigger) = self.required(criterion, "trigger") {
+						self.resource_location(trigger, false);
+					}
+					if let Some(conditions) = self.optional(criterion, "conditions") {
+						self.object(conditions);
+					}
+				}
+			}
+		}
+
+		if let Some(display) = self.optional(root, "display").and_then(|x| self.object(x)) {
+			if let Some(icon) = self.required(display, "icon").and_then(|x| self.object(x)) {
+				if let Some(item) = self.required(icon, "item") {
+					self.resource_location(item, false);
+				}
+			}
+			if let Some(title) = self.required(display, "title") {
+				self.text(title);
+			}
+			if let Some(description) = self.required(display, "description") {
+				self.text(description);
+			}
+			if let Some(frame) = self.optional(display, "frame") {
+				self.one_of(frame, &["task", "goal", "challenge"]);
+			}
+			if let Some(background) = self.optional(display, "background") {
+				self.resource_location(background, false);
+			}
+			for key in ["show_toast", "announce_to_chat", "hidden"].iter() {
+				if let Some(flag) = self.optional(display, key) {
+					self.boolean(flag);
+				}
+			}
+		}
+
+		if let Some(rewards) = self.optional(root, "rewards").and_then(|x| self.object(x)) {
+			for key in ["recipes", "loot"].iter() {
+				if let Some(list) = self.optional(rewards, key) {
+					self.each(list, |checker, x| {
+						checker.resource_location(x, false);
+					});
+				}
+			}
+			if let Some(experience) = self.optional(rewards, "experience") {
+				self.number(experience);
+			}
+			if let Some(function) = self.optional(rewards, "function") {
+				self.resource_location(function, false);
+			}
+		}
+
+		if let Some(requirements) = self.optional(root, "requirements") {
+			self.each(requirements, |checker, x| {
+				checker.each(x, |checker, x| {
+					checker.string(x);
+				})
+			});
+		}
+	}
+
+	fn ingredient(&mut self, value: &Value) {
+		if let Value::Array(array) = value {
+			if array.value.is_empty() {
+				self.errors.push(DatapackError::Empty(array.span));
+			}
+			array.value.iter().for_each(|x| self.ingredient(x));
+			return;
+		}
+
+		if let Some(object) = self.object(value) {
+			match (self.optional(object, "item"), self.optional(object, "tag")) {
+				(Some(location), _) | (None, Some(location)) => {
+					self.resource_location(location, false);
+				}
+				(None, None) => self.errors.push(DatapackError::MissingKey(object.span, "item")),
+			}
+		}
+	}
+
+	/// Result of a recipe, older version allow just a string
+	fn recipe_result(&mut self, value: &Value) {
+		match value {
+			Value::Literal(_) => {
+				self.resource_location(value, false);
+			}
+			_ => {
+				if let Some(result) = self.object(value) {
+					if let Some(item) = self.required(result, "item") {
+						self.resource_location(item, false);
+					}
+					if let Some(count) = self.optional(result, "count") {
+						self.number(count);
+					}
+				}
+			}
+		}
+	}
+
+	fn recipe(&mut self, value: &Value) {
+		let root = match self.object(value) {
+			Some(root) => root,
+			None => return,
+		};
+
+		let kind = match self.required(root, "type").and_then(|x| self.resource_location(x, false)) {
+			Some(kind) => kind,
+			None => return,
+		};
+		// Recipe types from other namespace could look like anything
+		if kind.namespace() != DEFAULT_NAMESPACE {
+			return;
+		}
+
+		match kind.path() {
+			"crafting_shaped" => {
+				if let Some(pattern) = self.required(root, "pattern") {
+					self.each(pattern, |checker, x| {
+						checker.string(x);
+					});
+				}
+				if let Some(key) = self.required(root, "key").and_then(|x| self.object(x)) {
+					key.value.values().for_each(|x| self.ingredient(x));
+				}
+			}
+			"crafting_shapeless" => {
+				if let Some(ingredients) = self.required(root, "ingredients") {
+					self.each(ingredients, Checker::ingredient);
+				}
+			}
+			"smelting" | "blasting" | "smoking" | "campfire_cooking" => {
+				if let Some(ingredient) = self.required(root, "ingredient") {
+					self.ingredient(ingredient);
+				}
+				for key in ["experience", "cookingtime"].iter() {
+					if let Some(number) = self.optional(root, key) {
+						self.number(number);
+					}
+				}
+			}
+			"stonecutting" => {
+				if let Some(ingredient) = self.required(root, "ingredient") {
+					self.ingredient(ingredient);
+				}
+				if let Some(count) = self.required(root, "count") {
+					self.number(count);
+				}
+			}
+			"smithing" => {
+				for key in ["base", "addition"].iter() {
+					if let Some(ingredient) = self.required(root, key) {
+						self.ingredient(ingredient);
+					}
+				}
+			}
+			// Special recipes don't have anything else
+			_ => return,
+		}
+
+		if let Some(result) = self.required(root, "result") {
+			self.recipe_result(result);
+		}
+	}
+
+	fn condition(&mut self, value: &Value) {
+		if let Some(object) = self.object(value) {
+			if let Some(condition) = self.required(object, "condition") {
+				self.resource_location(condition, false);
+			}
+		}
+	}
+
+	fn function(&mut self, value: &Value) {
+		if let Some(object) = self.object(value) {
+			if let Some(function) = self.required(object, "function") {
+				self.resource_location(function, false);
+			}
+			if let Some(conditions) = self.optional(object, "conditions") {
+				self.each(conditions, Checker::condition);
+			}
+		}
+	}
+
+	fn loot_entry(&mut self, value: &Value) {
+		if let Some(entry) = self.object(value) {
+			if let Some(kind) = self.required(entry, "type") {
+				self.resource_location(kind, false);
+			}
+			if let Some(children) = self.optional(entry, "children") {
+				self.each(children, Checker::loot_entry);
+			}
+			if let Some(conditions) = self.optional(entry, "conditions") {
+				self.each(conditions, Checker::condition);
+			}
+			if let Some(functions) = self.optional(entry, "functions") {
+				self.each(functions, Checker::function);
+			}
+		}
+	}
+
+	fn loot_table(&mut self, value: &Value) {
+		let root = match self.object(value) {
+			Some(root) => root,
+			None => return,
+		};
+
+		if let Some(kind) = self.optional(root, "type") {
+			self.resource_location(kind, false);
+		}
+		if let Some(functions) = self.optional(root, "functions") {
+			self.each(functions, Checker::function);
+		}
+
+		if let Some(pools) = self.optional(root, "pools") {
+			self.each(pools, |checker, pool| {
+				let pool = match checker.object(pool) {
+					Some(pool) => pool,
+					None => return,
+				};
+
+				// Rolls can be a number provider object as well
+				if let Some(rolls) = checker.required(pool, "rolls") {
+					if !rolls.is_number() && !rolls.is_object() {
+						checker.invalid_type(rolls, "number");
+					}
+				}
+				if let Some(entries) = checker.required(pool, "entries") {
+					checker.each(entries, Checker::loot_entry);
+				}
+				if let Some(conditions) = checker.optional(pool, "conditions") {
+					checker.each(conditions, Checker::condition);
+				}
+				if let Some(functions) = checker.optional(pool, "functions") {
+					checker.each(functions, Checker::function);
+				}
+			});
+		}
+	}
+
+	fn tag(&mut self, value: &Value) {
+		let root = match self.object(value) {
+			Some(root) => root,
+			None => return,
+		};
+
+		if let Some(replace) = self.optional(root, "replace") {
+			self.boolean(replace);
+		}
+
+		if let Some(values) = self.required(root, "values") {
+			self.each(values, |checker, x| match x {
+				Value::Object(entry) => {
+					if let Some(id) = checker.required(entry, "id") {
+						checker.resource_location(id, true);
+					}
+					if let Some(required) = checker.optional(entry, "required") {
+						checker.boolean(required);
+					}
+				}
+				_ => {
+					checker.resource_location(x, true);
+				}
+			});
+		}
+	}
+
+	fn pack_mcmeta(&mut self, value: &Value) {
+		let pack = self
+			.object(value)
+			.and_then(|root| self.required(root, "pack"))
+			.and_then(|x| self.object(x));
+
+		if let Some(pack) = pack {
+			if let Some(format) = self.required(pack, "pack_format") {
+				self.number(format);
+			}
+			if let Some(description) = self.required(pack, "description") {
+				self.text(description);
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::util;
+
+	fn check(kind: FileKind, content: &str) -> Result<(), Vec<DatapackError>> {
+		let value = util::from_str(content).expect("Invalid JSON");
+		validate(kind, &value)
+	}
+
+	#[test]
+	fn valid_advancement() {
+		let content = r#"
+		{
+			"display": {
+				"title": "Installed Datapacks",
+				"description": "",
+				"icon": {
+					"item": "minecraft:knowledge_book"
+				},
+				"background": "minecraft:textures/block/gray_concrete.png",
+				"show_toast": false,
+				"announce_to_chat": false
+			},
+			"criteria": {
+				"trigger": {
+					"trigger": "minecraft:tick"
+				}
+			}
+		}
+		"#;
+
+		assert_eq!(check(FileKind::Advancement, content), Ok(()));
+	}
+
+	#[test]
+	fn invalid_advancement() {
+		let content = r#"{ "display": { "title": "a", "icon": {}, "frame": "boss" }, "criteria": { "a": { "trigger": "Tick" } } }"#;
+		let mut errors = check(FileKind::Advancement, content).unwrap_err();
+		errors.sort_by_key(|x| x.to_string());
+
+		assert_eq!(
+			errors,
+			vec![
+				DatapackError::ResourceLocation(ResourceLocationError::InvalidPath(Span::new(93, 97), "Tick".to_owned())),
+				DatapackError::InvalidValue(Span::new(50, 56), "boss".to_owned()),
+				DatapackError::MissingKey(Span::new(13, 58), "description"),
+				DatapackError::MissingKey(Span::new(37, 39), "item"),
+			]
+		);
+	}
+
+	#[test]
+	fn recipe() {
+		let shaped = r###"
+		{
+			"type": "minecraft:crafting_shaped",
+			"pattern": ["##", "##"],
+			"key": { "#": { "tag": "minecraft:planks" } },
+			"result": { "item": "minecraft:crafting_table" }
+		}
+		"###;
+		assert_eq!(check(FileKind::Recipe, shaped), Ok(()));
+
+		let smelting = r#"{ "type": "smelting", "ingredient": [], "result": 5 }"#;
+		assert_eq!(
+			check(FileKind::Recipe, smelting),
+			Err(vec![
+				DatapackError::Empty(Span::new(36, 38)),
+				DatapackError::InvalidType(Span::new(50, 51), "object", "number"),
+			])
+		);
+	}
+
+	#[test]
+	fn loot_table() {
+		let content = r#"
+		{
+			"pools": [
+				{
+					"rolls": 1,
+					"entries": [{ "type": "minecraft:item", "name": "minecraft:diamond" }],
+					"conditions": [{ "condition": "minecraft:killed_by_player" }]
+				}
+			]
+		}
+		"#;
+		assert_eq!(check(FileKind::LootTable, content), Ok(()));
+
+		let content = r#"{ "pools": [{ "rolls": "1", "entries": [{}] }] }"#;
+		assert_eq!(
+			check(FileKind::LootTable, content),
+			Err(vec![
+				DatapackError::InvalidType(Span::new(23, 26), "number", "string"),
+				DatapackError::MissingKey(Span::new(40, 42), "type"),
+			])
+		);
+	}
+
+	#[test]
+	fn predicate_and_item_modifier() {
+		let predicate = r#"[{ "condition": "minecraft:random_chance", "chance": 0.5 }, {}]"#;
+		assert_eq!(
+			check(FileKind::Predicate, predicate),
+			Err(vec![DatapackError::MissingKey(Span::new(60, 62), "condition")])
+		);
+
+		let modifier = r#"{ "function": "minecraft:set_count", "count": 2 }"#;
+		assert_eq!(check(FileKind::ItemModifier, modifier), Ok(()));
+	}
+
+	#[test]
+	fn pack_mcmeta() {
+		let content = r#"{ "pack": { "pack_format": 6, "description": { "text": "Hello", "color": "pink" } } }"#;
+		assert_eq!(
+			check(FileKind::PackMcmeta, content),
+			Err(vec![DatapackError::Text(TextError::InvalidColor(Span::new(73, 79), "pink".to_owned()))])
+		);
+	}
+}
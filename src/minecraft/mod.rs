@@ -0,0 +1,31 @@
+use crate::value::{Literal, Value};
+
+/// Validators for each kind of file inside of a datapack
+pub mod datapack;
+mod resource_location;
+/// Minecraft's JSON text component
+pub mod text;
+
+pub use resource_location::{ResourceLocation, ResourceLocationError, DEFAULT_NAMESPACE};
+
+/// Get a string out of `value` with the span of the whole value
+pub(crate) fn as_literal(value: &Value) -> Option<Literal> {
+	match value {
+		Value::Literal(item) => Some(item.clone()),
+		_ => None,
+	}
+}
+
+/// Check if `value` is a valid resource location (`namespace:path` or just `path`), tags aren't allowed
+///
+/// ```
+/// # use json_peek::minecraft::is_resource_location;
+/// assert!(is_resource_location("minecraft:knowledge_book"));
+/// assert!(is_resource_location("textures/block/stone.png"));
+/// assert!(!is_resource_location("Minecraft:Stone"));
+/// assert!(!is_resource_location("foo:bar:baz"));
+/// assert!(!is_resource_location("#minecraft:logs"));
+/// ```
+pub fn is_resource_location(value: &str) -> bool {
+	matches!(ResourceLocation::parse(value), Ok(location) if !location.is_tag())
+}
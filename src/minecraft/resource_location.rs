@@ -0,0 +1,207 @@
+use crate::util::Span;
+use crate::value::{Literal, Value};
+use std::fmt;
+use thiserror::Error;
+
+/// Namespace that is used when resource location doesn't specify one
+pub const DEFAULT_NAMESPACE: &str = "minecraft";
+
+/// Problem found while parsing a [ResourceLocation](struct.ResourceLocation.html)
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+pub enum ResourceLocationError {
+	/// Value isn't a string, contain the type that was found instead
+	#[error("Expected resource location but found {1} at {0}")]
+	InvalidType(Span, &'static str),
+
+	/// Namespace contain character outside of `[a-z0-9_.-]`
+	#[error("Invalid namespace {1:?} at {0}")]
+	InvalidNamespace(Span, String),
+
+	/// Path is empty or contain character outside of `[a-z0-9_.-/]`
+	#[error("Invalid path {1:?} at {0}")]
+	InvalidPath(Span, String),
+
+	/// Tag reference (`#namespace:path`) is used where only a single resource is allowed
+	#[error("Tag isn't allowed here at {0}")]
+	UnexpectedTag(Span),
+}
+
+/// `namespace:path` string that Minecraft use to refer to its resources, optionally prefixed with `#` to refer to a tag
+///
+/// Namespace and path keep their own span so error can point at the exact part of the string.
+///
+/// ```
+/// # use json_peek::util;
+/// # use json_peek::util::Span;
+/// # use json_peek::minecraft::ResourceLocation;
+/// let value = util::from_str(r##""#minecraft:logs""##).unwrap();
+/// let location = ResourceLocation::from_value(&value).unwrap();
+///
+/// assert!(location.is_tag());
+/// assert_eq!(location.namespace(), "minecraft");
+/// assert_eq!(location.namespace_span(), Some(Span::new(2, 11)));
+/// assert_eq!(location.path(), "logs");
+/// assert_eq!(location.path_span(), Span::new(12, 16));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ResourceLocation {
+	span: Span,
+	is_tag: bool,
+	namespace: Option<Literal>,
+	path: Literal,
+}
+
+impl ResourceLocation {
+	/// Parse `value` as a resource location, spans are relative to the start of `value`
+	///
+	/// ```
+	/// # use json_peek::minecraft::ResourceLocation;
+	/// let location = ResourceLocation::parse("stone").unwrap();
+	/// assert_eq!(location.namespace(), "minecraft");
+	/// assert_eq!(location.namespace_span(), None);
+	/// assert_eq!(location.to_string(), "minecraft:stone");
+	///
+	/// assert!(ResourceLocation::parse("Minecraft:stone").is_err());
+	/// ```
+	pub fn parse(value: &str) -> Result<ResourceLocation, ResourceLocationError> {
+		ResourceLocation::parse_at(value, Span::new(0, value.len()), 0)
+	}
+
+	/// Parse the content of a string literal
+	///
+	/// Sub-spans assume that the string doesn't contain any escape sequence, which a valid resource location never need.
+	pub fn from_literal(literal: &Literal) -> Result<ResourceLocation, ResourceLocationError> {
+		let is_quoted = literal.span.len() == literal.value.len() + 2;
+		let offset = literal.span.start + if is_quoted { 1 } else { 0 };
+
+		ResourceLocation::parse_at(&literal.value, literal.span, offset)
+	}
+
+	pub fn from_value(value: &Value) -> Result<ResourceLocation, ResourceLocationError> {
+		match value {
+			Value::Literal(literal) => ResourceLocation::from_literal(literal),
+			_ => Err(ResourceLocationError::InvalidType(value.span(), value.kind_name())),
+		}
+	}
+
+	fn parse_at(value: &str, span: Span, offset: usize) -> Result<ResourceLocation, ResourceLocationError> {
+		let (is_tag, body, offset) = match value.strip_prefix('#') {
+			Some(body) => (true, body, offset + 1),
+			None => (false, value, offset),
+		};
+
+		let literal = |value: &str, start: usize| {
+			let mut literal = Literal::new_literal(value);
+			literal.span = Span::new(start, start + value.len());
+			literal
+		};
+
+		let (namespace, path) = match body.find(':') {
+			Some(index) => (
+				Some(literal(&body[..index], offset)),
+				literal(&body[index + 1..], offset + index + 1),
+			),
+			None => (None, literal(body, offset)),
+		};
+
+		if let Some(namespace) = &namespace {
+			if !namespace.value.chars().all(is_namespace_char) {
+				return Err(ResourceLocationError::InvalidNamespace(namespace.span, namespace.value.clone()));
+			}
+		}
+		if path.value.is_empty() || !path.value.chars().all(is_path_char) {
+			return Err(ResourceLocationError::InvalidPath(path.span, path.value));
+		}
+
+		Ok(ResourceLocation {
+			span,
+			is_tag,
+			namespace,
+			path,
+		})
+	}
+
+	/// Span of the whole string, including the quotes and `#`
+	pub fn span(&self) -> Span {
+		self.span
+	}
+
+	/// Is this a tag reference (`#namespace:path`)
+	pub fn is_tag(&self) -> bool {
+		self.is_tag
+	}
+
+	/// Namespace of this resource location, default to `minecraft` if it isn't specified
+	pub fn namespace(&self) -> &str {
+		self.namespace.as_ref().map_or(DEFAULT_NAMESPACE, |x| x.value.as_str())
+	}
+
+	/// Span of the namespace, `None` if the namespace isn't specified
+	pub fn namespace_span(&self) -> Option<Span> {
+		self.namespace.as_ref().map(|x| x.span)
+	}
+
+	pub fn path(&self) -> &str {
+		&self.path.value
+	}
+
+	pub fn path_span(&self) -> Span {
+		self.path.span
+	}
+}
+
+impl fmt::Display for ResourceLocation {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.is_tag {
+			write!(f, "#")?;
+		}
+		write!(f, "{}:{}", self.namespace(), self.path())
+	}
+}
+
+fn is_namespace_char(x: char) -> bool {
+	matches!(x, 'a'..='z' | '0'..='9' | '_' | '-' | '.')
+}
+
+fn is_path_char(x: char) -> bool {
+	is_namespace_char(x) || x == '/'
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::util;
+
+	#[test]
+	fn sub_spans() {
+		let value = util::from_str(r#"  "foo:bar/baz""#).unwrap();
+		let location = ResourceLocation::from_value(&value).unwrap();
+
+		assert!(!location.is_tag());
+		assert_eq!(location.span(), Span::new(2, 15));
+		assert_eq!(location.namespace_span(), Some(Span::new(3, 6)));
+		assert_eq!(location.path(), "bar/baz");
+		assert_eq!(location.path_span(), Span::new(7, 14));
+		assert_eq!(location.to_string(), "foo:bar/baz");
+	}
+
+	#[test]
+	fn invalid() {
+		assert_eq!(
+			ResourceLocation::parse("Foo:bar"),
+			Err(ResourceLocationError::InvalidNamespace(Span::new(0, 3), "Foo".to_owned()))
+		);
+		assert_eq!(
+			ResourceLocation::parse("#foo:"),
+			Err(ResourceLocationError::InvalidPath(Span::new(5, 5), "".to_owned()))
+		);
+		assert_eq!(
+			ResourceLocation::parse("foo:bar:baz"),
+			Err(ResourceLocationError::InvalidPath(Span::new(4, 11), "bar:baz".to_owned()))
+		);
+		assert_eq!(
+			ResourceLocation::from_value(&util::from_str("[]").unwrap()),
+			Err(ResourceLocationError::InvalidType(Span::new(0, 2), "array"))
+		);
+	}
+}
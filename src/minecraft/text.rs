@@ -0,0 +1,584 @@
+use super::as_literal;
+use crate::util::Span;
+use crate::value::{Bool, Item, Literal, Object, Value};
+use thiserror::Error;
+
+/// Named colors that Minecraft accept, `reset` included
+pub const COLORS: [&str; 17] = [
+	"black",
+	"dark_blue",
+	"dark_green",
+	"dark_aqua",
+	"dark_red",
+	"dark_purple",
+	"gold",
+	"gray",
+	"dark_gray",
+	"blue",
+	"green",
+	"aqua",
+	"red",
+	"light_purple",
+	"yellow",
+	"white",
+	"reset",
+];
+
+const CONTENT_KEYS: [&str; 14] = [
+	"text",
+	"translate",
+	"with",
+	"score",
+	"selector",
+	"separator",
+	"keybind",
+	"nbt",
+	"block",
+	"entity",
+	"storage",
+	"interpret",
+	"extra",
+	"type",
+];
+
+const STYLE_KEYS: [&str; 10] = [
+	"color",
+	"bold",
+	"italic",
+	"underlined",
+	"strikethrough",
+	"obfuscated",
+	"font",
+	"insertion",
+	"clickEvent",
+	"hoverEvent",
+];
+
+/// Problem found while interpreting a [Value](../../value/enum.Value.html) as a text component
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+pub enum TextError {
+	/// Value has the wrong type, contain what type was expected and what was found
+	#[error("Expected {1} but found {2} at {0}")]
+	InvalidType(Span, &'static str, &'static str),
+
+	/// Object has a key that text component doesn't know about
+	#[error("Unknown key {1:?} at {0}")]
+	UnknownKey(Span, String),
+
+	/// Object is missing a required key
+	#[error("Missing key {1:?} at {0}")]
+	MissingKey(Span, &'static str),
+
+	/// Object doesn't have any of `text`, `translate`, `score`, `selector`, `keybind` or `nbt`
+	#[error("Text component doesn't have any content at {0}")]
+	MissingContent(Span),
+
+	/// Color isn't one of the named colors or `#RRGGBB`
+	#[error("Invalid color {1:?} at {0}")]
+	InvalidColor(Span, String),
+
+	/// `clickEvent` or `hoverEvent` has an action that doesn't exist
+	#[error("Invalid action {1:?} at {0}")]
+	InvalidAction(Span, String),
+
+	/// Array form of text component need at least one component
+	#[error("Empty text component at {0}")]
+	Empty(Span),
+}
+
+/// Color of a text component
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+	/// One of the [COLORS](constant.COLORS.html)
+	Named(&'static str),
+	/// `#RRGGBB` color
+	Hex(u32),
+}
+
+impl Color {
+	/// Parse color's name or `#RRGGBB`
+	///
+	/// ```
+	/// # use json_peek::minecraft::text::Color;
+	/// assert_eq!(Color::parse("gold"), Some(Color::Named("gold")));
+	/// assert_eq!(Color::parse("#FF8000"), Some(Color::Hex(0xFF8000)));
+	/// assert_eq!(Color::parse("orange"), None);
+	/// ```
+	pub fn parse(value: &str) -> Option<Color> {
+		if let Some(hex) = value.strip_prefix('#') {
+			if hex.len() == 6 && hex.chars().all(|x| x.is_ascii_hexdigit()) {
+				return u32::from_str_radix(hex, 16).ok().map(Color::Hex);
+			}
+			return None;
+		}
+
+		COLORS.iter().find(|&&x| x == value).map(|&x| Color::Named(x))
+	}
+}
+
+/// What a text component display
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Content {
+	Text(Literal),
+	Translate {
+		key: Literal,
+		with: Vec<TextComponent>,
+	},
+	Score {
+		name: Literal,
+		objective: Literal,
+	},
+	Selector {
+		selector: Literal,
+		separator: Option<Box<TextComponent>>,
+	},
+	Keybind(Literal),
+	Nbt {
+		path: Literal,
+		/// The `block`, `entity` or `storage` key along with its value
+		source: Option<(Literal, Literal)>,
+		interpret: Option<Bool>,
+		separator: Option<Box<TextComponent>>,
+	},
+}
+
+/// `clickEvent` of a text component
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClickEvent {
+	pub span: Span,
+	pub action: Literal,
+	/// Can be either string or number (for `change_page`)
+	pub value: Value,
+}
+
+/// `hoverEvent` of a text component
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HoverEvent {
+	pub span: Span,
+	pub action: Literal,
+	pub contents: HoverContents,
+}
+
+/// Content of [HoverEvent](struct.HoverEvent.html), only `show_text` is interpreted further
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HoverContents {
+	Text(Box<TextComponent>),
+	Other(Value),
+}
+
+/// Formatting of a text component, every field is optional and inherit from the parent
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Style {
+	pub color: Option<Item<Color>>,
+	pub bold: Option<Bool>,
+	pub italic: Option<Bool>,
+	pub underlined: Option<Bool>,
+	pub strikethrough: Option<Bool>,
+	pub obfuscated: Option<Bool>,
+	pub font: Option<Literal>,
+	pub insertion: Option<Literal>,
+	pub click_event: Option<ClickEvent>,
+	pub hover_event: Option<HoverEvent>,
+}
+
+/// A Minecraft JSON text component, can be made from a string, an array or an object
+///
+/// ```
+/// # use json_peek::util;
+/// # use json_peek::minecraft::text::{Color, Content, TextComponent};
+/// let value = util::from_str(r#"{ "text": "Hello", "color": "gold", "extra": [", World!"] }"#).unwrap();
+/// let text = TextComponent::from_value(&value).unwrap();
+///
+/// assert_eq!(text.style.color.unwrap().value, Color::Named("gold"));
+/// assert_eq!(text.extra.len(), 1);
+/// ```
+///
+/// Every problem is collected instead of stopping at the first one
+/// ```
+/// # use json_peek::util;
+/// # use json_peek::util::Span;
+/// # use json_peek::minecraft::text::{TextComponent, TextError};
+/// let value = util::from_str(r#"{ "text": "Hello", "colour": "gold", "bold": 1 }"#).unwrap();
+/// let errors = TextComponent::from_value(&value).unwrap_err();
+///
+/// assert_eq!(errors.len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextComponent {
+	pub span: Span,
+	pub content: Content,
+	pub style: Style,
+	pub extra: Vec<TextComponent>,
+}
+
+impl TextComponent {
+	/// Interpret `value` as text component, return every problem found if it isn't one
+	pub fn from_value(value: &Value) -> Result<TextComponent, Vec<TextError>> {
+		let mut errors = Vec::new();
+		let result = component(value, &mut errors);
+
+		match result {
+			Some(text) if errors.is_empty() => Ok(text),
+			_ => Err(errors),
+		}
+	}
+}
+
+fn component(value: &Value, errors: &mut Vec<TextError>) -> Option<TextComponent> {
+	let span = value.span();
+	let plain = |content| TextComponent {
+		span,
+		content,
+		style: Style::default(),
+		extra: Vec::new(),
+	};
+
+	match value {
+		Value::Literal(item) => Some(plain(Content::Text(item.clone()))),
+		Value::Number(item) => Some(plain(Content::Text(item.clone()))),
+		Value::Bool(item) => Some(plain(Content::Text(Item::new(span, item.value.to_string())))),
+		Value::Array(item) => {
+			let mut list = item.value.iter().filter_map(|x| component(x, errors));
+			match list.next() {
+				Some(mut first) => {
+					first.extra.extend(list);
+					first.span = span;
+					Some(first)
+				}
+				None if item.value.is_empty() => {
+					errors.push(TextError::Empty(span));
+					None
+				}
+				None => None,
+			}
+		}
+		Value::Object(item) => object(item, errors),
+		Value::Null(_) => {
+			errors.push(TextError::InvalidType(span, "text component", value.kind_name()));
+			None
+		}
+	}
+}
+
+fn object(item: &Object, errors: &mut Vec<TextError>) -> Option<TextComponent> {
+	let span = item.span;
+	let get = |key: &str| item.value.get(&Literal::new_literal(key));
+
+	for key in item.value.keys() {
+		let name = key.value.as_str();
+		if !CONTENT_KEYS.contains(&name) && !STYLE_KEYS.contains(&name) {
+			errors.push(TextError::UnknownKey(key.span, key.value.clone()));
+		}
+	}
+
+	let style = style(item, errors);
+	let extra = match get("extra") {
+		Some(Value::Array(list)) => list.value.iter().filter_map(|x| component(x, errors)).collect(),
+		Some(value) => {
+			errors.push(TextError::InvalidType(value.span(), "array", value.kind_name()));
+			Vec::new()
+		}
+		None => Vec::new(),
+	};
+
+	let separator = |errors: &mut Vec<TextError>| {
+		get("separator").and_then(|x| component(x, errors)).map(Box::new)
+	};
+
+	let content = if let Some(text) = get("text") {
+		match text {
+			Value::Literal(item) | Value::Number(item) => Content::Text(item.clone()),
+			Value::Bool(item) => Content::Text(Item::new(item.span, item.value.to_string())),
+			_ => {
+				errors.push(TextError::InvalidType(text.span(), "string", text.kind_name()));
+				return None;
+			}
+		}
+	}
+	else if let Some(key) = get("translate") {
+		let key = string(key, errors)?;
+		let with = match get("with") {
+			Some(Value::Array(list)) => list.value.iter().filter_map(|x| component(x, errors)).collect(),
+			Some(value) => {
+				errors.push(TextError::InvalidType(value.span(), "array", value.kind_name()));
+				Vec::new()
+			}
+			None => Vec::new(),
+		};
+		Content::Translate { key, with }
+	}
+	else if let Some(score) = get("score") {
+		let score = match score {
+			Value::Object(score) => score,
+			_ => {
+				errors.push(TextError::InvalidType(score.span(), "object", score.kind_name()));
+				return None;
+			}
+		};
+		let field = |key: &'static str, errors: &mut Vec<TextError>| match score.value.get(&Literal::new_literal(key)) {
+			Some(value) => string(value, errors),
+			None => {
+				errors.push(TextError::MissingKey(score.span, key));
+				None
+			}
+		};
+		let name = field("name", errors);
+		let objective = field("objective", errors);
+		Content::Score {
+			name: name?,
+			objective: objective?,
+		}
+	}
+	else if let Some(selector) = get("selector") {
+		let selector = string(selector, errors)?;
+		Content::Selector {
+			selector,
+			separator: separator(errors),
+		}
+	}
+	else if let Some(keybind) = get("keybind") {
+		Content::Keybind(string(keybind, errors)?)
+	}
+	else if let Some(path) = get("nbt") {
+		let path = string(path, errors)?;
+		let source = ["block", "entity", "storage"].iter().find_map(|&key| {
+			let value = get(key)?;
+			let name = item.value.keys().find(|x| x.value == key)?.clone();
+			string(value, errors).map(|value| (name, value))
+		});
+		let interpret = match get("interpret") {
+			Some(Value::Bool(item)) => Some(*item),
+			Some(value) => {
+				errors.push(TextError::InvalidType(value.span(), "boolean", value.kind_name()));
+				None
+			}
+			None => None,
+		};
+		Content::Nbt {
+			path,
+			source,
+			interpret,
+			separator: separator(errors),
+		}
+	}
+	else {
+		errors.push(TextError::MissingContent(span));
+		return None;
+	};
+
+	Some(TextComponent {
+		span,
+		content,
+		style,
+		extra,
+	})
+}
+
+fn style(item: &Object, errors: &mut Vec<TextError>) -> Style {
+	let get = |key: &str| item.value.get(&Literal::new_literal(key));
+	let flag = |key: &str, errors: &mut Vec<TextError>| match get(key)? {
+		Value::Bool(item) => Some(*item),
+		value => {
+			errors.push(TextError::InvalidType(value.span(), "boolean", value.kind_name()));
+			None
+		}
+	};
+
+	let color = get("color").and_then(|value| {
+		let name = string(value, errors)?;
+		match Color::parse(&name.value) {
+			Some(color) => Some(Item::new(name.span, color)),
+			None => {
+				errors.push(TextError::InvalidColor(name.span, name.value));
+				None
+			}
+		}
+	});
+
+	Style {
+		color,
+		bold: flag("bold", errors),
+		italic: flag("italic", errors),
+		underlined: flag("underlined", errors),
+		strikethrough: flag("strikethrough", errors),
+		obfuscated: flag("obfuscated", errors),
+		font: get("font").and_then(|x| string(x, errors)),
+		insertion: get("insertion").and_then(|x| string(x, errors)),
+		click_event: get("clickEvent").and_then(|x| click_event(x, errors)),
+		hover_event: get("hoverEvent").and_then(|x| hover_event(x, errors)),
+	}
+}
+
+const CLICK_ACTIONS: [&str; 6] = [
+	"open_url",
+	"open_file",
+	"run_command",
+	"suggest_command",
+	"change_page",
+	"copy_to_clipboard",
+];
+
+const HOVER_ACTIONS: [&str; 3] = ["show_text", "show_item", "show_entity"];
+
+fn click_event(value: &Value, errors: &mut Vec<TextError>) -> Option<ClickEvent> {
+	let item = event(value, errors)?;
+	let action = action(item, &CLICK_ACTIONS, errors);
+	let value = match item.value.get(&Literal::new_literal("value")) {
+		Some(value @ Value::Literal(_)) | Some(value @ Value::Number(_)) => Some(value.clone()),
+		Some(value) => {
+			errors.push(TextError::InvalidType(value.span(), "string", value.kind_name()));
+			None
+		}
+		None => {
+			errors.push(TextError::MissingKey(item.span, "value"));
+			None
+		}
+	};
+
+	Some(ClickEvent {
+		span: item.span,
+		action: action?,
+		value: value?,
+	})
+}
+
+fn hover_event(value: &Value, errors: &mut Vec<TextError>) -> Option<HoverEvent> {
+	let item = event(value, errors)?;
+	let action = action(item, &HOVER_ACTIONS, errors)?;
+
+	// `value` is the legacy name of `contents`
+	let contents = item
+		.value
+		.get(&Literal::new_literal("contents"))
+		.or_else(|| item.value.get(&Literal::new_literal("value")));
+	let contents = match contents {
+		Some(value) if action.value == "show_text" => HoverContents::Text(Box::new(component(value, errors)?)),
+		Some(value) => HoverContents::Other(value.clone()),
+		None => {
+			errors.push(TextError::MissingKey(item.span, "contents"));
+			return None;
+		}
+	};
+
+	Some(HoverEvent {
+		span: item.span,
+		action,
+		contents,
+	})
+}
+
+fn event<'a>(value: &'a Value, errors: &mut Vec<TextError>) -> Option<&'a Object> {
+	match value {
+		Value::Object(item) => Some(item),
+		_ => {
+			errors.push(TextError::InvalidType(value.span(), "object", value.kind_name()));
+			None
+		}
+	}
+}
+
+fn action(item: &Object, actions: &[&str], errors: &mut Vec<TextError>) -> Option<Literal> {
+	let action = match item.value.get(&Literal::new_literal("action")) {
+		Some(value) => string(value, errors)?,
+		None => {
+			errors.push(TextError::MissingKey(item.span, "action"));
+			return None;
+		}
+	};
+
+	if actions.contains(&action.value.as_str()) {
+		Some(action)
+	}
+	else {
+		errors.push(TextError::InvalidAction(action.span, action.value));
+		None
+	}
+}
+
+fn string(value: &Value, errors: &mut Vec<TextError>) -> Option<Literal> {
+	let result = as_literal(value);
+	if result.is_none() {
+		errors.push(TextError::InvalidType(value.span(), "string", value.kind_name()));
+	}
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::util;
+
+	fn parse(content: &str) -> Result<TextComponent, Vec<TextError>> {
+		let value = util::from_str(content).expect("Invalid JSON");
+		TextComponent::from_value(&value)
+	}
+
+	#[test]
+	fn plain_text() {
+		let text = parse(r#""Hello""#).unwrap();
+		assert_eq!(text.content, Content::Text(Literal::new_literal("Hello")));
+		assert_eq!(text.span, Span::new(0, 7));
+	}
+
+	#[test]
+	fn array_text() {
+		let text = parse(r#"["Hello", {"text": ", ", "bold": true}, {"translate": "world", "with": ["a"]}]"#).unwrap();
+
+		assert_eq!(text.content, Content::Text(Literal::new_literal("Hello")));
+		assert_eq!(text.extra.len(), 2);
+		assert_eq!(text.extra[0].style.bold, Some(Bool::new_bool(true)));
+		match &text.extra[1].content {
+			Content::Translate { key, with } => {
+				assert_eq!(key, &Literal::new_literal("world"));
+				assert_eq!(with[0].content, Content::Text(Literal::new_literal("a")));
+			}
+			content => panic!("Unexpected content: {:?}", content),
+		}
+	}
+
+	#[test]
+	fn events() {
+		let text = parse(
+			r##"{
+				"score": { "name": "@s", "objective": "kills" },
+				"color": "#00FF00",
+				"clickEvent": { "action": "run_command", "value": "/say hi" },
+				"hoverEvent": { "action": "show_text", "contents": { "text": "hi", "italic": false } }
+			}"##,
+		)
+		.unwrap();
+
+		assert_eq!(text.style.color.map(|x| x.value), Some(Color::Hex(0x00FF00)));
+		assert_eq!(text.style.click_event.unwrap().action, Literal::new_literal("run_command"));
+		match text.style.hover_event.unwrap().contents {
+			HoverContents::Text(text) => assert_eq!(text.style.italic, Some(Bool::new_bool(false))),
+			contents => panic!("Unexpected hover contents: {:?}", contents),
+		}
+	}
+
+	#[test]
+	fn invalid_text() {
+		let content = r#"{ "text": "a", "color": "orange", "foo": 1, "clickEvent": { "action": "explode", "value": "" }, "extra": [[]] }"#;
+		let mut errors = parse(content).unwrap_err();
+		errors.sort_by_key(|x| x.to_string());
+
+		assert_eq!(
+			errors,
+			vec![
+				TextError::Empty(Span::new(106, 108)),
+				TextError::InvalidAction(Span::new(70, 79), "explode".to_owned()),
+				TextError::InvalidColor(Span::new(24, 32), "orange".to_owned()),
+				TextError::UnknownKey(Span::new(34, 39), "foo".to_owned()),
+			]
+		);
+	}
+
+	#[test]
+	fn missing_content() {
+		assert_eq!(parse(r#"{ "bold": true }"#), Err(vec![TextError::MissingContent(Span::new(0, 16))]));
+		assert_eq!(
+			parse(r#"{ "score": { "name": "@s" } }"#),
+			Err(vec![TextError::MissingKey(Span::new(11, 27), "objective")])
+		);
+	}
+}
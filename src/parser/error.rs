@@ -1,4 +1,5 @@
-use crate::lexer::Token;
+use super::Fix;
+use crate::lexer::{LexError, Token};
 use crate::util::Span;
 use thiserror::Error;
 
@@ -8,15 +9,82 @@ use thiserror::Error;
 #[derive(Debug, Error, PartialEq, Eq, Clone)]
 pub enum ParseError<'a> {
 	/// This error is raise when the parser expect another [Token](../lexer/struct.Token.html)
-	/// but reach the end of file instead
+	/// but reach the end of file instead, fixes close every unclosed container
 	#[error("Unexpected End-Of-File at {0}")]
-	UnexpectedEndOfFile(Span),
+	UnexpectedEndOfFile(Span, Vec<Fix>),
 
 	/// This error is raise when parser is trying to parse a [Token](../lexer/struct.Token.html) that isn't in JSON syntax specification
 	#[error("Invalid Token at {0}")]
-	InvalidToken(Token<'a>),
+	InvalidToken(Token<'a>, Vec<Fix>),
 
 	/// This error is raise when parser is expecting a certain [Token](../lexer/struct.Token.html) but found something else.
 	#[error("Unexpected Token: {0}, expecting: {1}")]
-	UnexpectedToken(Token<'a>, Token<'a>),
+	UnexpectedToken(Token<'a>, Token<'a>, Vec<Fix>),
+
+	/// This error is raise when the lexer couldn't make a valid [Token](../lexer/struct.Token.html) out of part of the source,
+	/// see [LexError](../lexer/enum.LexError.html) for the reason
+	#[error("{1} at {0}")]
+	MalformedToken(Span, LexError),
+
+	/// This error is raise when objects and arrays are nested deeper than [Limits::max_depth](struct.Limits.html#structfield.max_depth)
+	#[error("Nesting depth limit exceeded at {0}")]
+	DepthLimitExceeded(Span),
+
+	/// This error is raise when a string is longer than [Limits::max_string_length](struct.Limits.html#structfield.max_string_length)
+	#[error("String is too long at {0}")]
+	StringTooLong(Span),
+
+	/// This error is raise when a number is longer than [Limits::max_number_length](struct.Limits.html#structfield.max_number_length)
+	#[error("Number is too long at {0}")]
+	NumberTooLong(Span),
+
+	/// This error is raise when the input has more token than [Limits::max_tokens](struct.Limits.html#structfield.max_tokens)
+	#[error("Too many tokens at {0}")]
+	TooManyTokens(Span),
+
+	/// This error is raise when the input is bigger than [Limits::max_input_size](struct.Limits.html#structfield.max_input_size),
+	/// the span cover everything after the limit
+	#[error("Input is too large at {0}")]
+	InputTooLarge(Span),
+}
+
+impl ParseError<'_> {
+	/// Position in the source where this error happened
+	///
+	/// ```
+	/// # use json_peek::parser::Parser;
+	/// # use json_peek::util::Span;
+	/// let error = Parser::new(r#"["foo": 1]"#).parse().unwrap_err();
+	///
+	/// assert_eq!(error.span(), Span::new(6, 7));
+	/// ```
+	pub fn span(&self) -> Span {
+		match self {
+			Self::InvalidToken(token, _) | Self::UnexpectedToken(token, ..) => token.full_span(),
+			Self::UnexpectedEndOfFile(span, _)
+			| Self::MalformedToken(span, _)
+			| Self::DepthLimitExceeded(span)
+			| Self::StringTooLong(span)
+			| Self::NumberTooLong(span)
+			| Self::TooManyTokens(span)
+			| Self::InputTooLarge(span) => *span,
+		}
+	}
+
+	/// Suggestions that would get rid of this error, apply them with [apply_fixes()](fn.apply_fixes.html)
+	///
+	/// ```
+	/// # use json_peek::parser::{self, Parser};
+	/// let source = r#"{"foo": [1, 2,]}"#;
+	/// let error = Parser::new(source).parse().unwrap_err();
+	///
+	/// assert_eq!(error.fixes()[0].message, "Remove this comma");
+	/// assert_eq!(parser::apply_fixes(source, error.fixes()), r#"{"foo": [1, 2]}"#);
+	/// ```
+	pub fn fixes(&self) -> &[Fix] {
+		match self {
+			Self::UnexpectedEndOfFile(_, fixes) | Self::InvalidToken(_, fixes) | Self::UnexpectedToken(.., fixes) => fixes,
+			_ => &[],
+		}
+	}
 }
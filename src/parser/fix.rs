@@ -0,0 +1,130 @@
+use super::{Parser, TextEdit};
+
+/// How confident a [Fix](struct.Fix.html) is about what the source should have been
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Applicability {
+	/// Can be applied without asking, like removing a trailing comma
+	Safe,
+	/// Make the source parse but might not be what the author meant, like swapping a mismatched bracket
+	Unsafe,
+}
+
+/// Machine applicable suggestion attached to a [ParseError](enum.ParseError.html)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+	pub message: &'static str,
+	pub edit: TextEdit<'static>,
+	pub applicability: Applicability,
+}
+
+impl Fix {
+	pub const fn new(message: &'static str, edit: TextEdit<'static>, applicability: Applicability) -> Fix {
+		Fix {
+			message,
+			edit,
+			applicability,
+		}
+	}
+
+	pub fn is_safe(&self) -> bool {
+		self.applicability == Applicability::Safe
+	}
+}
+
+/// Apply `fixes` to `source`, a fix that overlap with an earlier one is skipped
+///
+/// ```
+/// # use json_peek::parser::{self, Applicability, Fix, TextEdit};
+/// let fixes = [
+///     Fix::new("Insert `,` here", TextEdit::new(2..2, ","), Applicability::Safe),
+///     Fix::new("Add closing `]`", TextEdit::new(4..4, "]"), Applicability::Safe),
+/// ];
+///
+/// assert_eq!(parser::apply_fixes("[1 2", &fixes), "[1, 2]");
+/// ```
+pub fn apply_fixes<'f>(source: &str, fixes: impl IntoIterator<Item = &'f Fix>) -> String {
+	let mut fixes: Vec<_> = fixes.into_iter().collect();
+	fixes.sort_by_key(|x| x.edit.range.start);
+
+	let mut result = String::with_capacity(source.len());
+	let mut cursor = 0;
+	for fix in fixes {
+		let range = &fix.edit.range;
+		if range.start < cursor || range.end > source.len() {
+			continue;
+		}
+		result.push_str(&source[cursor..range.start]);
+		result.push_str(fix.edit.text);
+		cursor = range.end;
+	}
+	result.push_str(&source[cursor..]);
+	result
+}
+
+impl Parser<'_> {
+	/// Keep parsing `source` and applying every [safe](enum.Applicability.html#variant.Safe) fix until it parse
+	/// or there is no safe fix left, this is what a `--fix` mode would do
+	///
+	/// ```
+	/// # use json_peek::parser::Parser;
+	/// assert_eq!(Parser::fix(r#"{"a": [1 2,], "b": 3,"#), r#"{"a": [1, 2], "b": 3}"#);
+	/// ```
+	pub fn fix(source: &str) -> String {
+		let mut source = source.to_owned();
+		loop {
+			let fixed = match Parser::new(&source).parse() {
+				Ok(_) => return source,
+				Err(error) => apply_fixes(&source, error.fixes().iter().filter(|x| x.is_safe())),
+			};
+			if fixed == source {
+				return source;
+			}
+			source = fixed;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::parser::ParseError;
+
+	fn fixes(source: &str) -> Vec<(&'static str, std::ops::Range<usize>, Applicability)> {
+		let error = Parser::new(source).parse().unwrap_err();
+		error.fixes().iter().map(|x| (x.message, x.edit.range.clone(), x.applicability)).collect()
+	}
+
+	#[test]
+	fn suggestions() {
+		assert_eq!(fixes(r#"{"a": 1,}"#), [("Remove this comma", 7..8, Applicability::Safe)]);
+		assert_eq!(fixes(r#"[1, {"a" 2}]"#), [("Insert `:` here", 8..8, Applicability::Safe)]);
+		assert_eq!(fixes(r#"[1, 2}"#), [("Replace with `]`", 5..6, Applicability::Unsafe)]);
+		assert_eq!(
+			fixes(r#"{"a": [{"b": 1"#),
+			[
+				("Add closing `}`", 14..14, Applicability::Safe),
+				("Add closing `]`", 14..14, Applicability::Safe),
+				("Add closing `}`", 14..14, Applicability::Safe),
+			]
+		);
+		assert!(matches!(Parser::new("[1, :]").parse(), Err(ParseError::InvalidToken(_, fixes)) if fixes.is_empty()));
+	}
+
+	#[test]
+	fn insert_only_before_value() {
+		assert_eq!(fixes("[1 null]"), [("Insert `,` here", 2..2, Applicability::Safe)]);
+		assert_eq!(fixes("[1 nil]"), []);
+		assert!(fixes("[1 /* a */]").iter().all(|x| x.2 != Applicability::Safe));
+		assert_eq!(Parser::fix("[1.5e-3]"), "[1.5e-3]");
+
+		assert_eq!(fixes(r#"{"a": 1 "b": 2}"#), [("Insert `,` here", 7..7, Applicability::Safe)]);
+		assert_eq!(fixes(r#"{"a": 1 [2]}"#), []);
+		assert_eq!(Parser::fix(r#"{"a": 1 [2]}"#), r#"{"a": 1 [2]}"#);
+	}
+
+	#[test]
+	fn fix_stop_without_safe_fix() {
+		assert_eq!(Parser::fix("[1, 2}"), "[1, 2}");
+		assert_eq!(Parser::fix(r#"{"a" "b" [1,]"#), r#"{"a": "b" [1,]"#);
+	}
+}
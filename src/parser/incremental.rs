@@ -0,0 +1,310 @@
+use super::{Limits, ParseResult, Parser};
+use crate::util::Span;
+use crate::value::{Item, Origin, Value};
+use std::collections::HashMap;
+use std::mem;
+use std::ops::Range;
+
+/// A single change to the source text: replace `range` of the *old* source with `text`
+///
+/// `range` is a byte range with an exclusive end, so an insertion is an empty range.
+///
+/// ```
+/// # use json_peek::parser::TextEdit;
+/// let edit = TextEdit::new(3..3, "42");
+///
+/// assert_eq!(edit.delta(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit<'a> {
+	pub range: Range<usize>,
+	pub text: &'a str,
+}
+
+impl<'a> TextEdit<'a> {
+	pub const fn new(range: Range<usize>, text: &'a str) -> TextEdit<'a> {
+		TextEdit { range, text }
+	}
+
+	/// How much the source length change after applying this edit
+	pub fn delta(&self) -> isize {
+		self.text.len() as isize - self.range.len() as isize
+	}
+
+	/// Check if this edit is entirely inside of a container's brackets
+	fn is_inside(&self, span: Span) -> bool {
+		self.range.start > span.start && self.range.end < span.end
+	}
+}
+
+impl<'a> Parser<'a> {
+	/// Reparse `source` after `edit` was applied to the source `previous` was parsed from.
+	///
+	/// Only the smallest object or array that contain the edit will be re-lexed and parsed again,
+	/// everything else is reused with its [Span](../util/struct.Span.html) shifted and its [Origin](../value/enum.Origin.html) kept.
+	/// If that's not possible (or the smaller parse failed) this will fallback to parsing the whole `source`.
+	///
+	/// ```
+	/// # use json_peek::parser::{Parser, TextEdit};
+	/// # use json_peek::value::prelude::*;
+	/// let old = r#"{"foo": [1, 2], "bar": 3}"#;
+	/// let previous = Parser::new(old).parse().unwrap();
+	///
+	/// let new = r#"{"foo": [1, 20], "bar": 3}"#;
+	/// let edit = TextEdit::new(13..13, "0");
+	/// let result = Parser::reparse(&previous, new, &edit).unwrap();
+	///
+	/// assert_eq!(result.get("foo").index(1).unwrap(), "20");
+	/// assert_eq!(result.get("bar").unwrap().span(), Parser::new(new).parse().unwrap().get("bar").unwrap().span());
+	/// ```
+	pub fn reparse(previous: &Value, source: &'a str, edit: &TextEdit) -> ParseResult<'a> {
+		Parser::reparse_with_limits(previous, source, edit, Limits::default())
+	}
+
+	/// Same as [reparse()](#method.reparse) but fail on the same `limits` as [with_limits()](#method.with_limits)
+	///
+	/// The smaller parse still count the depth from the root of the document.
+	/// [max_tokens](struct.Limits.html#structfield.max_tokens) and [max_input_size](struct.Limits.html#structfield.max_input_size)
+	/// cover the whole source so setting them always parse the whole `source`.
+	pub fn reparse_with_limits(previous: &Value, source: &'a str, edit: &TextEdit, limits: Limits) -> ParseResult<'a> {
+		let is_whole = limits.max_tokens != usize::MAX || limits.max_input_size != usize::MAX;
+		let value = if is_whole { None } else { splice(previous, source, edit, &limits, 0) };
+
+		match value {
+			Some(value) => Ok(value),
+			None => Parser::with_limits(source, limits).parse(),
+		}
+	}
+}
+
+/// Rebuild `node` with the edit applied, return `None` if the edit isn't inside of `node` or the reparse failed
+///
+/// `depth` is how many containers `node` is nested in.
+fn splice(node: &Value, source: &str, edit: &TextEdit, limits: &Limits, depth: usize) -> Option<Value> {
+	if !edit.is_inside(node.span()) {
+		return None;
+	}
+
+	let delta = edit.delta();
+	let is_after = |value: &Value| value.span().start >= edit.range.end;
+	let update = |value: &Value| {
+		let mut value = value.clone();
+		if is_after(&value) {
+			shift(&mut value, delta);
+		}
+		value
+	};
+
+	match node {
+		Value::Object(item) if item.value.values().any(|x| is_container(x) && edit.is_inside(x.span())) => {
+			let list = item
+				.value
+				.iter()
+				.map(|(key, value)| {
+					let value = if is_container(value) && edit.is_inside(value.span()) {
+						splice(value, source, edit, limits, depth + 1)?
+					} else {
+						update(value)
+					};
+
+					let mut key = key.clone();
+					if key.span.start >= edit.range.end {
+						move_span(&mut key, delta);
+					}
+					Some((key, value))
+				})
+				.collect::<Option<HashMap<_, _>>>()?;
+
+			Some(Value::Object(Item {
+				span: stretch(item.span, delta),
+				value: list,
+				origin: item.origin,
+			}))
+		}
+		Value::Array(item) if item.value.iter().any(|x| is_container(x) && edit.is_inside(x.span())) => {
+			let list = item
+				.value
+				.iter()
+				.map(|value| {
+					if is_container(value) && edit.is_inside(value.span()) {
+						splice(value, source, edit, limits, depth + 1)
+					} else {
+						Some(update(value))
+					}
+				})
+				.collect::<Option<Vec<_>>>()?;
+
+			Some(Value::Array(Item {
+				span: stretch(item.span, delta),
+				value: list,
+				origin: item.origin,
+			}))
+		}
+		Value::Object(_) | Value::Array(_) => reparse_container(node.span(), source, delta, limits, depth),
+		_ => None,
+	}
+}
+
+/// Parse only the text of the container at `span` (before the edit) inside of the new `source`,
+/// the container is nested `depth` deep so that much is taken off the depth limit
+fn reparse_container(span: Span, source: &str, delta: isize, limits: &Limits, depth: usize) -> Option<Value> {
+	let span = stretch(span, delta);
+	let content = source.get(span.range())?;
+
+	let limits = Limits {
+		max_depth: limits.max_depth.checked_sub(depth)?,
+		..*limits
+	};
+	let mut parser = Parser::with_limits(content, limits);
+	let value = parser.parse().ok()?;
+
+	// The container must still end at the same bracket, otherwise the surrounding structure changed too
+	if parser.inner.next().is_some() || value.span().end != content.len() {
+		return None;
+	}
+
+	let mut value = value;
+	shift(&mut value, span.start as isize);
+	Some(value)
+}
+
+fn is_container(value: &Value) -> bool {
+	value.is_object() || value.is_array()
+}
+
+fn stretch(span: Span, by: isize) -> Span {
+	Span::new(span.start, span.end_point().shift(by).end)
+}
+
+/// Move every span inside of `value` by `by` bytes
+fn shift(value: &mut Value, by: isize) {
+	match value {
+		Value::Object(item) => {
+			move_span(item, by);
+			item.value = mem::take(&mut item.value)
+				.into_iter()
+				.map(|(mut key, mut value)| {
+					move_span(&mut key, by);
+					shift(&mut value, by);
+					(key, value)
+				})
+				.collect();
+		}
+		Value::Array(item) => {
+			move_span(item, by);
+			item.value.iter_mut().for_each(|value| shift(value, by));
+		}
+		Value::Literal(item) => move_span(item, by),
+		Value::Number(item) => move_span(item, by),
+		Value::Bool(item) => move_span(item, by),
+		Value::Null(item) => move_span(item, by),
+	}
+}
+
+/// Synthetic items don't point anywhere in the source so they stay where they are
+fn move_span<T>(item: &mut Item<T>, by: isize) {
+	if item.origin != Origin::Synthetic {
+		item.span = item.span.shift(by);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::parser::ParseError;
+	use crate::value::{Indexable, Keyable};
+
+	fn apply(source: &str, edit: &TextEdit) -> String {
+		let mut result = source.to_owned();
+		result.replace_range(edit.range.clone(), edit.text);
+		result
+	}
+
+	#[test]
+	fn reparse_nested_value() {
+		let old = r#"{ "foo": { "bar": [1, 2, 3] }, "baz": "hello" }"#;
+		let previous = Parser::new(old).parse().unwrap();
+
+		let edit = TextEdit::new(22..23, "200");
+		let new = apply(old, &edit);
+		let result = Parser::reparse(&previous, &new, &edit).unwrap();
+		let expected = Parser::new(&new).parse().unwrap();
+
+		assert_eq!(result, expected);
+		assert_eq!(result.get("foo").get("bar").index(1).unwrap().span(), Span::new(22, 25));
+		assert_eq!(result.get("baz").unwrap().span(), expected.get("baz").unwrap().span());
+		assert_eq!(result.span(), expected.span());
+	}
+
+	#[test]
+	fn reparse_removed_entry() {
+		let old = r#"[{ "a": 1, "b": 2 }, [3]]"#;
+		let previous = Parser::new(old).parse().unwrap();
+
+		let edit = TextEdit::new(9..17, "");
+		let new = apply(old, &edit);
+		let result = Parser::reparse(&previous, &new, &edit).unwrap();
+		let expected = Parser::new(&new).parse().unwrap();
+
+		assert_eq!(result, expected);
+		assert_eq!(result.index(1).index(0).unwrap().span(), expected.index(1).index(0).unwrap().span());
+	}
+
+	#[test]
+	fn reparse_keep_origin() {
+		let old = r#"{ "foo": [1, 2], "bar": 3 }"#;
+		let mut previous = Parser::new(old).parse().unwrap();
+		let object = previous.as_object_mut().unwrap();
+		object.insert("baz", Value::from("new"));
+		*object.get_mut("bar").unwrap() = Value::from(30);
+
+		let edit = TextEdit::new(14..14, "0");
+		let new = apply(old, &edit);
+		let result = Parser::reparse(&previous, &new, &edit).unwrap();
+
+		assert_eq!(result.origin(), Origin::Derived);
+		assert_eq!(result.get("foo").unwrap().origin(), Origin::Source);
+		assert_eq!(result.get("bar").unwrap().origin(), Origin::Synthetic);
+		assert_eq!(result.get("bar").unwrap().span(), Span::new(0, 0));
+		assert_eq!(result.get("baz").unwrap().source_span(), None);
+	}
+
+	#[test]
+	fn reparse_depth_limit() {
+		let depth = 100;
+		let old = format!("{}1{}", "[".repeat(depth), "]".repeat(depth));
+		let previous = Parser::new(&old).parse().unwrap();
+
+		let text = format!("{}1{}", "[".repeat(50), "]".repeat(50));
+		let edit = TextEdit::new(depth..depth + 1, &text);
+		let new = apply(&old, &edit);
+		let limit = Limits::DEFAULT_DEPTH;
+
+		assert_eq!(Parser::reparse(&previous, &new, &edit), Err(ParseError::DepthLimitExceeded(Span::new(limit, limit + 1))));
+		assert_eq!(Parser::reparse(&previous, &new, &edit), Parser::new(&new).parse());
+
+		let limits = Limits {
+			max_depth: 160,
+			..Limits::default()
+		};
+		let result = Parser::reparse_with_limits(&previous, &new, &edit, limits).unwrap();
+		assert_eq!(result, Parser::with_limits(&new, limits).parse().unwrap());
+
+		let limits = Limits {
+			max_tokens: 10,
+			..Limits::none()
+		};
+		assert_eq!(Parser::reparse_with_limits(&previous, &new, &edit, limits), Parser::with_limits(&new, limits).parse());
+	}
+
+	#[test]
+	fn reparse_fallback_on_structure_change() {
+		let old = r#"{ "foo": [1, 2] }"#;
+		let previous = Parser::new(old).parse().unwrap();
+
+		let edit = TextEdit::new(14..15, "");
+		let new = apply(old, &edit);
+
+		assert_eq!(Parser::reparse(&previous, &new, &edit), Parser::new(&new).parse());
+	}
+}
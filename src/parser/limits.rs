@@ -0,0 +1,56 @@
+/// Limits that [Parser](struct.Parser.html) will enforce while parsing, useful when the input can't be trusted
+///
+/// Only the depth is limited by default, use struct update syntax to only set the ones you care about.
+///
+/// ```
+/// # use json_peek::parser::{Limits, Parser, ParseError};
+/// # use json_peek::util::Span;
+/// let limits = Limits {
+///     max_depth: 2,
+///     ..Limits::default()
+/// };
+///
+/// let mut parser = Parser::with_limits("[[[1]]]", limits);
+/// assert_eq!(parser.parse(), Err(ParseError::DepthLimitExceeded(Span::new(2, 3))));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Limits {
+	/// Maximum amount of nested objects and arrays, [128](#associatedconstant.DEFAULT_DEPTH) by default
+	///
+	/// The parser itself doesn't care but dropping, cloning, comparing or printing a [Value](../value/enum.Value.html) is recursive,
+	/// so a tree that is too deep can still overflow the stack after it was parsed.
+	pub max_depth: usize,
+	/// Maximum length of a string literal in bytes, not including the quotes
+	pub max_string_length: usize,
+	/// Maximum length of a number literal in bytes
+	pub max_number_length: usize,
+	/// Maximum amount of tokens in the whole input
+	pub max_tokens: usize,
+	/// Maximum size of the whole input in bytes
+	pub max_input_size: usize,
+}
+
+impl Limits {
+	pub const DEFAULT_DEPTH: usize = 128;
+
+	/// Limits that doesn't limit anything, only use this on input that you trust
+	/// since a deeply nested tree can overflow the stack once it's dropped
+	pub const fn none() -> Limits {
+		Limits {
+			max_depth: usize::MAX,
+			max_string_length: usize::MAX,
+			max_number_length: usize::MAX,
+			max_tokens: usize::MAX,
+			max_input_size: usize::MAX,
+		}
+	}
+}
+
+impl Default for Limits {
+	fn default() -> Limits {
+		Limits {
+			max_depth: Limits::DEFAULT_DEPTH,
+			..Limits::none()
+		}
+	}
+}
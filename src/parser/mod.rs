@@ -1,123 +1,308 @@
 use crate::{
-	lexer::{Lexer, LexerIter, Token, TokenKind},
+	lexer::{ByteLexerIter, Lexer, Token, TokenKind},
 	util::Span,
 	value::{Literal, Value},
 };
 use std::collections::HashMap;
+use std::iter::Peekable;
 
 mod error;
+mod fix;
+mod incremental;
+mod limits;
 pub use error::ParseError;
+pub use fix::{apply_fixes, Applicability, Fix};
+pub use incremental::TextEdit;
+pub use limits::Limits;
 
 pub type ParseResult<'a> = Result<Value, ParseError<'a>>;
 
 /// A struct for creating JSON's syntax tree from [Tokens](../lexer/struct.Token.html)
+///
+/// Nested objects and arrays are kept on the heap instead of the call stack
+/// so deeply nested input can't overflow the stack, see [Limits](struct.Limits.html) to reject them early.
 pub struct Parser<'a> {
-	inner: LexerIter<'a>,
+	inner: Peekable<ByteLexerIter<'a>>,
 	pos: Span,
+	/// Last two tokens, used to suggest fixes
+	previous: Option<Token<'a>>,
+	current: Option<Token<'a>>,
+	source: &'a str,
+	limits: Limits,
+	tokens: usize,
 }
 
 impl<'a> Parser<'a> {
 
 	/// Create new Parser from given string
 	/// 
-	/// Note: This method also create [LexerIter](../lexer/struct.LexerIter.html) as well
+	/// Note: This method also create [ByteLexerIter](../lexer/struct.ByteLexerIter.html) as well
 	pub fn new(source: &'a str) -> Parser<'a> {
-		let inner = Lexer::new(source).into_iter();
+		Parser::with_limits(source, Limits::default())
+	}
+
+	/// Create new Parser from given string which will fail once it hit any of the `limits`
+	pub fn with_limits(source: &'a str, limits: Limits) -> Parser<'a> {
+		let inner = Lexer::new(source).byte_iter().peekable();
 		let pos = Span::default();
-		Parser { inner, pos }
+		Parser {
+			inner,
+			pos,
+			previous: None,
+			current: None,
+			source,
+			limits,
+			tokens: 0,
+		}
 	}
 
 	pub fn parse(&mut self) -> ParseResult<'a> {
-		let token: Token = self
-			.inner
-			.next()
-			.ok_or(ParseError::UnexpectedEndOfFile(self.pos))?;
-		self.pos = token.span;
-
-		if token == '{' {
-			self.parse_object(token)
-		}
-		else if token == '[' {
-			self.parse_array(token)
-		}
-		else {
-			self.parse_other(token)
+		if self.source.len() > self.limits.max_input_size {
+			let span = Span::new(self.limits.max_input_size, self.source.len());
+			return Err(ParseError::InputTooLarge(span));
 		}
+
+		let mut stack: Vec<Frame<'a>> = Vec::new();
+		self.parse_value(&mut stack).map_err(|error| match error {
+			ParseError::UnexpectedEndOfFile(span, _) => ParseError::UnexpectedEndOfFile(span, self.closing_fixes(&stack)),
+			error => error,
+		})
 	}
 
-	fn parse_object(&mut self, token: Token) -> ParseResult<'a> {
-		let mut list = HashMap::default();
-		let mut last_token = token;
+	/// Parse a single value, containers that are still open are left in `stack` when this fail
+	fn parse_value(&mut self, stack: &mut Vec<Frame<'a>>) -> ParseResult<'a> {
+		loop {
+			let token = self.expect_token()?;
 
-		while let Some(token) = self.inner.next() {
-			if token.kind == TokenKind::Symbol {
-				return Err(ParseError::InvalidToken(token));
+			let mut value = if token.is_symbol('{') {
+				self.check_depth(stack.len(), token)?;
+				if let Some(last) = self.next_closing('}')? {
+					Value::new_object(Span::from_span(token.span, last.span), HashMap::default())
+				}
+				else {
+					stack.push(Frame::Object(token, token, HashMap::default(), None));
+					self.next_key(stack)?;
+					continue;
+				}
 			}
-
-			let key = Literal::from(token);
-
-			if let Some(token) = self.inner.next() {
-				if token != ':' {
-					return Err(ParseError::UnexpectedToken(token, Token::test_symbol(":")));
+			else if token.is_symbol('[') {
+				self.check_depth(stack.len(), token)?;
+				if let Some(last) = self.next_closing(']')? {
+					Value::new_array(Span::from_span(token.span, last.span), Vec::default())
+				}
+				else {
+					stack.push(Frame::Array(token, token, Vec::default()));
+					continue;
 				}
 			}
+			else {
+				self.parse_other(token)?
+			};
+
+			// Put the finished value into its parent, closing every container that end right after it
+			loop {
+				let frame = match stack.last_mut() {
+					Some(frame) => frame,
+					None => return Ok(value),
+				};
+				frame.push(value);
+
+				let token = self.expect_token()?;
+				frame.set_last(token);
+
+				if token.is_symbol(frame.closing()) {
+					value = stack.pop().unwrap().finish();
+					continue;
+				}
+				else if !token.is_symbol(',') {
+					return Err(self.unexpected_token(token, ",", frame.closing()));
+				}
 
-			let value = self.parse()?;
-			list.insert(key, value);
+				self.next_key(stack)?;
+				break;
+			}
+		}
+	}
+
+	/// Get the next token or fail if this is the end of file
+	fn expect_token(&mut self) -> Result<Token<'a>, ParseError<'a>> {
+		self.next_token()?
+			.ok_or_else(|| ParseError::UnexpectedEndOfFile(self.pos, Vec::new()))
+	}
 
-			let token = self
-				.inner
-				.next();
+	/// Get the next token while making sure it's within the limits
+	fn next_token(&mut self) -> Result<Option<Token<'a>>, ParseError<'a>> {
+		let token = match self.inner.next() {
+			Some(token) => token,
+			None => return Ok(None),
+		};
 
-			let token = dbg!(token).ok_or(ParseError::UnexpectedEndOfFile(self.pos))?;
-			last_token = token;
+		self.pos = token.full_span();
+		self.previous = self.current.replace(token);
+		self.tokens += 1;
+		if self.tokens > self.limits.max_tokens {
+			return Err(ParseError::TooManyTokens(token.full_span()));
+		}
 
-			if token == '}' {
-				break;
+		match token.kind {
+			TokenKind::Error(error) => Err(ParseError::MalformedToken(token.span, error)),
+			TokenKind::Comment => Err(ParseError::InvalidToken(token, Vec::new())),
+			TokenKind::String if token.value().len() > self.limits.max_string_length => {
+				Err(ParseError::StringTooLong(token.full_span()))
 			}
-			else if token != ',' {
-				return Err(ParseError::UnexpectedToken(token, Token::test_symbol(",")));
+			TokenKind::Number if token.value().len() > self.limits.max_number_length => {
+				Err(ParseError::NumberTooLong(token.full_span()))
 			}
+			_ => Ok(Some(token)),
+		}
+	}
+
+	/// Consume the next token only if it's the `closing` bracket of an empty container
+	fn next_closing(&mut self, closing: char) -> Result<Option<Token<'a>>, ParseError<'a>> {
+		match self.inner.peek() {
+			Some(token) if token.is_symbol(closing) => self.next_token(),
+			_ => Ok(None),
+		}
+	}
+
+	/// Check if opening another container at `depth` would go over the depth limit
+	fn check_depth(&self, depth: usize, token: Token<'a>) -> Result<(), ParseError<'a>> {
+		if depth >= self.limits.max_depth {
+			Err(ParseError::DepthLimitExceeded(token.span))
+		}
+		else {
+			Ok(())
 		}
+	}
 
-		let span = Span::from_span(token.span, last_token.span);
-		Ok(Value::new_object(span, list))
+	/// Parse the key of the upcoming value if the innermost container is an object
+	fn next_key(&mut self, stack: &mut [Frame<'a>]) -> Result<(), ParseError<'a>> {
+		if let Some(Frame::Object(.., key)) = stack.last_mut() {
+			*key = Some(self.parse_key()?);
+		}
+		Ok(())
 	}
 
-	fn parse_array(&mut self, token: Token) -> ParseResult<'a> {
-		let mut list = Vec::default();
+	/// Parse object's key and the following colon
+	fn parse_key(&mut self) -> Result<Literal, ParseError<'a>> {
+		let token = self.expect_token()?;
 
-		list.push(self.parse()?);
-		let mut last_token = token;
+		if token.kind != TokenKind::String {
+			return Err(self.invalid_token(token));
+		}
 
-		while let Some(token) = self.inner.next() {
-			last_token = token;
+		let key = Literal::from(token);
 
-			if token == ']' {
-				break;
-			}
-			else if token != ',' {
-				return Err(ParseError::UnexpectedToken(token, Token::test_symbol(",")));
-			}
-			list.push(self.parse()?);
+		let token = self.expect_token()?;
+		if !token.is_symbol(':') {
+			return Err(self.unexpected_token(token, ":", '}'));
 		}
 
-		let span = Span::from_span(token.span, last_token.span);
-		Ok(Value::new_array(span, list))
+		Ok(key)
 	}
 
 	fn parse_other(&self, token: Token<'a>) -> ParseResult<'a> {
-		Option::<Value>::from(token).ok_or(ParseError::InvalidToken(token))
+		Option::<Value>::from(token).ok_or_else(|| self.invalid_token(token))
+	}
+
+	/// [ParseError::InvalidToken](enum.ParseError.html#variant.InvalidToken) for `token`, suggest removing the comma before a closing bracket
+	fn invalid_token(&self, token: Token<'a>) -> ParseError<'a> {
+		let mut fixes = Vec::new();
+		if let Some(previous) = self.previous.filter(|x| x.is_symbol(',')) {
+			if token.is_symbol('}') || token.is_symbol(']') {
+				fixes.push(Fix::new("Remove this comma", TextEdit::new(previous.span.start..previous.span.end, ""), Applicability::Safe));
+			}
+		}
+		ParseError::InvalidToken(token, fixes)
+	}
+
+	/// [ParseError::UnexpectedToken](enum.ParseError.html#variant.UnexpectedToken) for `token` when `expected` should be there instead,
+	/// suggest inserting `expected` if `token` start a value (or a key inside of an object) or swapping a bracket that doesn't match `closing`
+	fn unexpected_token(&self, token: Token<'a>, expected: &'static str, closing: char) -> ParseError<'a> {
+		let mut fixes = Vec::new();
+		let starts_value = match token.kind {
+			// Next member of an object start with its key
+			_ if expected == "," && closing == '}' => token.kind == TokenKind::String,
+			TokenKind::String | TokenKind::Number => true,
+			TokenKind::Identifier => matches!(token.value(), "true" | "false" | "null"),
+			_ => token.is_symbol('{') || token.is_symbol('['),
+		};
+
+		if let (true, Some(previous)) = (starts_value, self.previous) {
+			let end = previous.full_span().end;
+			let message = match expected {
+				"," => "Insert `,` here",
+				_ => "Insert `:` here",
+			};
+			fixes.push(Fix::new(message, TextEdit::new(end..end, expected), Applicability::Safe));
+		}
+		else if expected == "," && (token.is_symbol('}') || token.is_symbol(']')) {
+			let (message, text) = match closing {
+				'}' => ("Replace with `}`", "}"),
+				_ => ("Replace with `]`", "]"),
+			};
+			fixes.push(Fix::new(message, TextEdit::new(token.span.start..token.span.end, text), Applicability::Unsafe));
+		}
+
+		ParseError::UnexpectedToken(token, Token::test_symbol(expected), fixes)
+	}
+
+	/// Close every container in `stack` at the end of the source, innermost first
+	fn closing_fixes(&self, stack: &[Frame<'a>]) -> Vec<Fix> {
+		let end = self.source.len();
+		stack
+			.iter()
+			.rev()
+			.map(|frame| {
+				let (message, text) = match frame.closing() {
+					'}' => ("Add closing `}`", "}"),
+					_ => ("Add closing `]`", "]"),
+				};
+				Fix::new(message, TextEdit::new(end..end, text), Applicability::Safe)
+			})
+			.collect()
 	}
 }
 
-#[cfg(test)]
-macro_rules! hashmap {
-	($($x:expr => $y:expr)* ) => {
-		{
-			let mut m = HashMap::new();
-			$(m.insert($x, $y);)*
-			m
+/// An object or array that is still being parsed, along with its opening and last seen token and the key of the upcoming value
+enum Frame<'a> {
+	Object(Token<'a>, Token<'a>, HashMap<Literal, Value>, Option<Literal>),
+	Array(Token<'a>, Token<'a>, Vec<Value>),
+}
+
+impl<'a> Frame<'a> {
+	fn push(&mut self, value: Value) {
+		match self {
+			Frame::Object(_, _, list, key) => {
+				if let Some(key) = key.take() {
+					list.insert(key, value);
+				}
+			}
+			Frame::Array(_, _, list) => list.push(value),
+		}
+	}
+
+	fn set_last(&mut self, token: Token<'a>) {
+		match self {
+			Frame::Object(_, last, ..) | Frame::Array(_, last, _) => *last = token,
+		}
+	}
+
+	fn closing(&self) -> char {
+		match self {
+			Frame::Object(..) => '}',
+			Frame::Array(..) => ']',
+		}
+	}
+
+	fn finish(self) -> Value {
+		match self {
+			Frame::Object(first, last, list, _) => {
+				Value::new_object(Span::from_span(first.span, last.span), list)
+			}
+			Frame::Array(first, last, list) => {
+				Value::new_array(Span::from_span(first.span, last.span), list)
+			}
 		}
 	}
 }
@@ -125,6 +310,7 @@ macro_rules! hashmap {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::json;
 
 	#[test]
 	fn try_parse_stuff() {
@@ -138,12 +324,7 @@ mod tests {
 		let mut parser = Parser::new(content);
 		let result = parser.parse().unwrap();
 
-		let map = hashmap! {
-			Literal::new_literal("foo") => Value::test_number("42")
-			Literal::new_literal("bar") => Value::test_number("0")
-		};
-
-		assert_eq!(result, Value::test_object(map));
+		assert_eq!(result, json!({ "foo": 42, "bar": 0 }));
 	}
 
 	#[test]
@@ -164,13 +345,13 @@ mod tests {
 
 		assert_eq!(
 			result,
-			Value::test_object(hashmap! {
-				Literal::new_literal("foo") => Value::test_number("42")
-				Literal::new_literal("bar") => Value::test_object(hashmap! {
-					Literal::new_literal("a") => Value::test_array(vec![Value::test_number("1"), Value::test_number("2"), Value::test_number("3")])
-					Literal::new_literal("b") => Value::test_bool(false)
-				})
-				Literal::new_literal("baz") => Value::test_null()
+			json!({
+				"foo": 42,
+				"bar": {
+					"a": [1, 2, 3],
+					"b": false
+				},
+				"baz": null
 			})
 		);
 	}
@@ -187,9 +368,13 @@ mod tests {
 		let mut parser = Parser::new(content);
 		let result = parser.parse();
 
+		let comma = content.rfind(',').unwrap();
 		assert_eq!(
 			result,
-			Err(ParseError::InvalidToken(Token::test_symbol("}")))
+			Err(ParseError::InvalidToken(
+				Token::test_symbol("}"),
+				vec![Fix::new("Remove this comma", TextEdit::new(comma..comma + 1, ""), Applicability::Safe)]
+			))
 		);
 	}
 
@@ -209,8 +394,121 @@ mod tests {
 			result,
 			Err(ParseError::UnexpectedToken(
 				Token::test_symbol(":"),
-				Token::test_symbol(",")
+				Token::test_symbol(","),
+				Vec::new()
 			))
 		);
 	}
+
+	#[test]
+	fn parse_deeply_nested_array() {
+		let content = "[".repeat(100_000) + &"]".repeat(100_000);
+
+		let mut parser = Parser::new(&content);
+		let result = parser.parse();
+
+		assert_eq!(result, Err(ParseError::DepthLimitExceeded(Span::new(128, 129))));
+	}
+
+	#[test]
+	fn parse_deeply_nested_unclosed_array() {
+		let content = "[".repeat(100_000);
+
+		let mut parser = Parser::with_limits(&content, Limits::none());
+		let result = parser.parse();
+
+		assert!(matches!(result, Err(ParseError::UnexpectedEndOfFile(span, fixes)) if span == Span::new(99_999, 100_000) && fixes.len() == 100_000));
+	}
+
+	#[test]
+	fn parse_and_drop_nested_array() {
+		let depth = Limits::DEFAULT_DEPTH;
+		let content = "[".repeat(depth) + &"]".repeat(depth);
+
+		let value = Parser::new(&content).parse().unwrap();
+		assert_eq!(value.depth_first().count(), depth);
+		drop(value);
+
+		let content = "[".repeat(depth + 1) + &"]".repeat(depth + 1);
+		assert_eq!(Parser::new(&content).parse(), Err(ParseError::DepthLimitExceeded(Span::new(depth, depth + 1))));
+	}
+
+	#[test]
+	fn parse_with_depth_limit() {
+		let content = "[".repeat(100_000);
+		let limits = Limits {
+			max_depth: 64,
+			..Limits::default()
+		};
+
+		let mut parser = Parser::with_limits(&content, limits);
+		let result = parser.parse();
+
+		assert_eq!(result, Err(ParseError::DepthLimitExceeded(Span::new(64, 65))));
+	}
+
+	#[test]
+	fn parse_with_size_limits() {
+		let content = r#"{ "foo": "megumin", "bar": 12345 }"#;
+		let parse = |limits| Parser::with_limits(content, limits).parse();
+
+		assert_eq!(
+			parse(Limits { max_string_length: 4, ..Limits::default() }),
+			Err(ParseError::StringTooLong(Span::new(9, 18)))
+		);
+		assert_eq!(
+			parse(Limits { max_number_length: 4, ..Limits::default() }),
+			Err(ParseError::NumberTooLong(Span::new(27, 32)))
+		);
+		assert_eq!(
+			parse(Limits { max_tokens: 3, ..Limits::default() }),
+			Err(ParseError::TooManyTokens(Span::new(9, 18)))
+		);
+		assert_eq!(
+			parse(Limits { max_input_size: 10, ..Limits::default() }),
+			Err(ParseError::InputTooLarge(Span::new(10, 34)))
+		);
+	}
+
+	#[test]
+	fn parse_unclosed_container() {
+		let close = |text| vec![Fix::new("Add closing `}`", TextEdit::new(text..text, "}"), Applicability::Safe)];
+		let error = Parser::new("[1, 2").parse().unwrap_err();
+		assert_eq!(error.span(), Span::new(4, 5));
+		assert_eq!(error.fixes()[0].edit, TextEdit::new(5..5, "]"));
+		assert_eq!(Parser::new(r#"{"foo""#).parse(), Err(ParseError::UnexpectedEndOfFile(Span::new(1, 6), close(6))));
+	}
+
+	#[test]
+	fn parse_with_comments() {
+		let is_comment = |source| matches!(Parser::new(source).parse(), Err(ParseError::InvalidToken(token, _)) if token.kind == TokenKind::Comment);
+
+		assert!(is_comment("// c\n1"));
+		assert!(is_comment("[/* c */ 1]"));
+		assert!(is_comment("[1 /* c */]"));
+		assert!(is_comment("[1, // c\n 2]"));
+		assert!(is_comment(r#"{/*x*/: 1}"#));
+		assert!(is_comment("{// c\n: 1}"));
+		assert!(is_comment(r#"{"a" /* c */: 1}"#));
+		assert!(is_comment(r#"{"a": /* c */ 1}"#));
+		assert!(is_comment(r#"{"a": 1 /* c */}"#));
+	}
+
+	#[test]
+	fn parse_non_string_key() {
+		assert_eq!(Parser::new("{a: 1}").parse().unwrap_err().span(), Span::new(1, 2));
+		assert_eq!(Parser::new("{1: 1}").parse().unwrap_err().span(), Span::new(1, 2));
+		assert_eq!(Parser::new(r#"{"a": 1, null: 2}"#).parse().unwrap_err().span(), Span::new(9, 13));
+	}
+
+	#[test]
+	fn parse_malformed_token() {
+		use crate::lexer::LexError;
+
+		assert_eq!(Parser::new("[1, 01]").parse(), Err(ParseError::MalformedToken(Span::new(4, 6), LexError::InvalidNumber)));
+		assert_eq!(
+			Parser::new("{\"a\": \u{2018}b\u{2019}}").parse(),
+			Err(ParseError::MalformedToken(Span::new(6, 9), LexError::UnexpectedCharacter('\u{2018}')))
+		);
+	}
 }
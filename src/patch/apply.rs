@@ -0,0 +1,287 @@
+use super::pointer::parse_index;
+use super::{JsonPointer, Operation, Patch, PointerError};
+use crate::util::{escape, unescape, Span};
+use crate::value::{Item, Literal, Object, Value};
+use thiserror::Error;
+
+/// Problem found while reading or applying a [Patch](struct.Patch.html), spans point into the patch document
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+pub enum PatchError {
+	/// Part of the patch document has the wrong type, contain what type was expected
+	#[error("Expected {1} at {0}")]
+	InvalidType(Span, &'static str),
+
+	/// Operation is missing a required member
+	#[error("Missing member {1:?} at {0}")]
+	MissingMember(Span, &'static str),
+
+	/// `op` isn't one of `add`, `remove`, `replace`, `move`, `copy` or `test`
+	#[error("Unknown operation {1:?} at {0}")]
+	UnknownOperation(Span, String),
+
+	#[error("Invalid JSON pointer at {0}: {1}")]
+	InvalidPointer(Span, PointerError),
+
+	/// Operation refer to a location that doesn't exist
+	#[error("Path {1:?} doesn't exist, from operation at {0}")]
+	PathNotFound(Span, String),
+
+	/// Array index that isn't a number or is out of bound
+	#[error("Invalid array index {1:?}, from operation at {0}")]
+	InvalidIndex(Span, String),
+
+	/// `test` operation found a different value
+	#[error("Test failed at {1:?}, from operation at {0}")]
+	TestFailed(Span, String),
+
+	/// `move` operation try to move a value into one of its children
+	#[error("Cannot move a value into itself, from operation at {0}")]
+	MoveIntoChild(Span),
+}
+
+impl PatchError {
+	/// Span of the patch operation or the part of it that cause this error
+	pub fn span(&self) -> Span {
+		match self {
+			PatchError::InvalidType(span, _)
+			| PatchError::MissingMember(span, _)
+			| PatchError::UnknownOperation(span, _)
+			| PatchError::InvalidPointer(span, _)
+			| PatchError::PathNotFound(span, _)
+			| PatchError::InvalidIndex(span, _)
+			| PatchError::TestFailed(span, _)
+			| PatchError::MoveIntoChild(span) => *span,
+		}
+	}
+}
+
+impl Patch {
+	/// Read a JSON Patch document
+	///
+	/// ```
+	/// # use json_peek::util;
+	/// # use json_peek::util::Span;
+	/// # use json_peek::patch::{Patch, PatchError};
+	/// let patch = util::from_str(r#"[
+	///     { "op": "test", "path": "/a", "value": 1 },
+	///     { "op": "remove", "path": "/a" }
+	/// ]"#).unwrap();
+	/// let patch = Patch::from_value(&patch).unwrap();
+	///
+	/// let value = util::from_str(r#"{ "a": 1, "b": 2 }"#).unwrap();
+	/// assert_eq!(patch.apply(&value).unwrap().to_string(), r#"{"b":2}"#);
+	///
+	/// let value = util::from_str(r#"{ "a": 2 }"#).unwrap();
+	/// assert_eq!(patch.apply(&value), Err(PatchError::TestFailed(Span::new(6, 48), "/a".to_owned())));
+	/// ```
+	pub fn from_value(value: &Value) -> Result<Patch, PatchError> {
+		let array = match value {
+			Value::Array(array) => array,
+			_ => return Err(PatchError::InvalidType(value.span(), "array")),
+		};
+
+		let operations = array.value.iter().map(operation).collect::<Result<_, _>>()?;
+		Ok(Patch { operations })
+	}
+
+	/// Apply every operation in order, `value` is left untouched if any of them fail.
+	/// Containers changed by the patch are marked as [derived](../value/enum.Origin.html#variant.Derived).
+	pub fn apply(&self, value: &Value) -> Result<Value, PatchError> {
+		let mut result = value.clone();
+		for operation in &self.operations {
+			apply(&mut result, operation)?;
+		}
+		Ok(result)
+	}
+}
+
+fn operation(value: &Value) -> Result<Item<Operation>, PatchError> {
+	let object = match value {
+		Value::Object(object) => object,
+		_ => return Err(PatchError::InvalidType(value.span(), "object")),
+	};
+
+	let member = |key: &'static str| {
+		object
+			.value
+			.iter()
+			.find(|(x, _)| unescape(&x.value) == key)
+			.map(|(_, value)| value)
+			.ok_or(PatchError::MissingMember(object.span, key))
+	};
+	let string = |key: &'static str| match member(key)? {
+		Value::Literal(literal) => Ok(literal),
+		other => Err(PatchError::InvalidType(other.span(), "string")),
+	};
+	let pointer = |key: &'static str| {
+		let literal = string(key)?;
+		JsonPointer::parse(&unescape(&literal.value)).map_err(|error| PatchError::InvalidPointer(literal.span, error))
+	};
+
+	let op = string("op")?;
+	let path = pointer("path")?;
+
+	let operation = match unescape(&op.value).as_ref() {
+		"add" => Operation::Add { path, value: member("value")?.clone() },
+		"remove" => Operation::Remove { path },
+		"replace" => Operation::Replace { path, value: member("value")?.clone() },
+		"move" => Operation::Move { from: pointer("from")?, path },
+		"copy" => Operation::Copy { from: pointer("from")?, path },
+		"test" => Operation::Test { path, value: member("value")?.clone() },
+		_ => return Err(PatchError::UnknownOperation(op.span, op.value.clone())),
+	};
+
+	Ok(Item::new(object.span, operation))
+}
+
+fn apply(target: &mut Value, operation: &Item<Operation>) -> Result<(), PatchError> {
+	let span = operation.span;
+	let not_found = |path: &JsonPointer| PatchError::PathNotFound(span, path.to_string());
+
+	match &operation.value {
+		Operation::Add { path, value } => add(target, path, value.clone(), span),
+		Operation::Remove { path } => remove(target, path, span).map(|_| ()),
+		Operation::Replace { path, value } => {
+			let current = target.pointer_mut(path).ok_or_else(|| not_found(path))?;
+			*current = value.clone();
+			if !path.is_root() {
+				parent(target, path, span)?.0.mark_derived();
+			}
+			Ok(())
+		}
+		Operation::Move { from, path } => {
+			if path.tokens().starts_with(from.tokens()) && path != from {
+				return Err(PatchError::MoveIntoChild(span));
+			}
+			let value = remove(target, from, span)?;
+			add(target, path, value, span)
+		}
+		Operation::Copy { from, path } => {
+			let value = target.pointer(from).ok_or_else(|| not_found(from))?.clone();
+			add(target, path, value, span)
+		}
+		Operation::Test { path, value } => {
+			let current = target.pointer(path).ok_or_else(|| not_found(path))?;
+			if current.semantic_eq(value) {
+				Ok(())
+			}
+			else {
+				Err(PatchError::TestFailed(span, path.to_string()))
+			}
+		}
+	}
+}
+
+/// Get the container that `path` is inside of along with the last token of `path`
+fn parent<'v, 'p>(target: &'v mut Value, path: &'p JsonPointer, span: Span) -> Result<(&'v mut Value, &'p str), PatchError> {
+	let (parent, last) = path.split_last().ok_or_else(|| PatchError::PathNotFound(span, path.to_string()))?;
+	let container = target.pointer_mut(&parent).ok_or_else(|| PatchError::PathNotFound(span, parent.to_string()))?;
+	Ok((container, last))
+}
+
+fn add(target: &mut Value, path: &JsonPointer, value: Value, span: Span) -> Result<(), PatchError> {
+	if path.is_root() {
+		*target = value;
+		return Ok(());
+	}
+
+	let (container, last) = parent(target, path, span)?;
+	container.mark_derived();
+	match container {
+		Value::Object(object) => {
+			match find_key(object, last) {
+				Some(key) => object.value.insert(key, value),
+				None => object.value.insert(Literal::new_literal(escape(last)), value),
+			};
+			Ok(())
+		}
+		Value::Array(array) => {
+			let index = match last {
+				"-" => array.value.len(),
+				_ => parse_index(last).ok_or_else(|| PatchError::InvalidIndex(span, last.to_owned()))?,
+			};
+			if index > array.value.len() {
+				return Err(PatchError::InvalidIndex(span, last.to_owned()));
+			}
+			array.value.insert(index, value);
+			Ok(())
+		}
+		_ => Err(PatchError::PathNotFound(span, path.to_string())),
+	}
+}
+
+fn remove(target: &mut Value, path: &JsonPointer, span: Span) -> Result<Value, PatchError> {
+	let not_found = || PatchError::PathNotFound(span, path.to_string());
+	let (container, last) = parent(target, path, span)?;
+	container.mark_derived();
+
+	match container {
+		Value::Object(object) => {
+			let key = find_key(object, last).ok_or_else(not_found)?;
+			object.value.remove(&key).ok_or_else(not_found)
+		}
+		Value::Array(array) => match parse_index(last) {
+			Some(index) if index < array.value.len() => Ok(array.value.remove(index)),
+			_ => Err(PatchError::InvalidIndex(span, last.to_owned())),
+		},
+		_ => Err(not_found()),
+	}
+}
+
+/// Find the key of `object` that is equal to `token` after unescaping
+fn find_key(object: &Object, token: &str) -> Option<Literal> {
+	object.value.keys().find(|key| unescape(&key.value) == token).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::util;
+
+	fn patch(document: &str, patch: &str) -> Result<String, PatchError> {
+		let document = util::from_str(document).unwrap();
+		let patch = Patch::from_value(&util::from_str(patch).unwrap())?;
+		patch.apply(&document).map(|x| x.to_string())
+	}
+
+	#[test]
+	fn operations() {
+		assert_eq!(patch(r#"{"a": [1, 3]}"#, r#"[{"op": "add", "path": "/a/1", "value": 2}]"#), Ok(r#"{"a":[1,2,3]}"#.to_owned()));
+		assert_eq!(patch(r#"[1]"#, r#"[{"op": "add", "path": "/-", "value": 2}]"#), Ok("[1,2]".to_owned()));
+		assert_eq!(patch(r#"[1, 2]"#, r#"[{"op": "replace", "path": "/0", "value": [0]}]"#), Ok("[[0],2]".to_owned()));
+		assert_eq!(
+			patch(r#"{"a": {"b": 1}, "c": []}"#, r#"[{"op": "move", "from": "/a/b", "path": "/c/0"}, {"op": "remove", "path": "/a"}]"#),
+			Ok(r#"{"c":[1]}"#.to_owned())
+		);
+		assert_eq!(patch(r#"[1]"#, r#"[{"op": "copy", "from": "/0", "path": "/0"}]"#), Ok("[1,1]".to_owned()));
+		assert_eq!(patch(r#"{}"#, r#"[{"op": "add", "path": "", "value": null}]"#), Ok("null".to_owned()));
+	}
+
+	#[test]
+	fn apply_diff() {
+		let old = util::from_str(r#"{"a": [1, 2, 3, {"x": 1}], "b": {"c": true}, "d": "gone"}"#).unwrap();
+		let new = util::from_str(r#"{"a": [0, 2, {"x": 2}, 4], "b": {"c": true, "e": []}}"#).unwrap();
+
+		let patch = crate::patch::Diff::new(&old, &new).to_patch();
+		assert!(patch.apply(&old).unwrap().semantic_eq(&new));
+	}
+
+	#[test]
+	fn errors() {
+		assert_eq!(
+			patch("{}", r#"[{"op": "remove", "path": "/a"}]"#),
+			Err(PatchError::PathNotFound(Span::new(1, 31), "/a".to_owned()))
+		);
+		assert_eq!(patch("[]", r#"[{"op": "add", "path": "/1", "value": 1}]"#), Err(PatchError::InvalidIndex(Span::new(1, 40), "1".to_owned())));
+		assert_eq!(patch("{}", r#"[{"op": "jump", "path": ""}]"#), Err(PatchError::UnknownOperation(Span::new(8, 14), "jump".to_owned())));
+		assert_eq!(patch("{}", r#"[{"op": "add", "path": ""}]"#), Err(PatchError::MissingMember(Span::new(1, 26), "value")));
+		assert_eq!(
+			patch("{}", r#"[{"op": "remove", "path": "a"}]"#),
+			Err(PatchError::InvalidPointer(Span::new(26, 29), PointerError::MissingSlash))
+		);
+		assert_eq!(
+			patch(r#"{"a": {}}"#, r#"[{"op": "move", "from": "/a", "path": "/a/b"}]"#),
+			Err(PatchError::MoveIntoChild(Span::new(1, 45)))
+		);
+	}
+}
@@ -0,0 +1,277 @@
+use super::{JsonPointer, Operation, Patch};
+use crate::util::{unescape, Span};
+use crate::value::{Array, Object, Value};
+use std::borrow::Cow;
+
+/// Arrays bigger than this (old length × new length) are compared item by item instead of finding the longest common subsequence
+const MAX_LCS_SIZE: usize = 1 << 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+	Added,
+	Removed,
+	Changed,
+}
+
+/// Single difference between two [Value](../value/enum.Value.html)s
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change<'a> {
+	pub kind: ChangeKind,
+	/// Like JSON Patch, this is relative to the document after every previous change has been applied
+	pub path: JsonPointer,
+	/// Value in the old document, `None` if it's added
+	pub old: Option<&'a Value>,
+	/// Value in the new document, `None` if it's removed
+	pub new: Option<&'a Value>,
+	/// Span in the old source, for added value this is the span of the container it's added to
+	pub old_span: Span,
+	/// Span in the new source, for removed value this is the span of the container it's removed from
+	pub new_span: Span,
+}
+
+impl Change<'_> {
+	pub fn to_operation(&self) -> Operation {
+		let path = self.path.clone();
+
+		match (self.kind, self.new) {
+			(ChangeKind::Added, Some(value)) => Operation::Add { path, value: value.clone() },
+			(ChangeKind::Changed, Some(value)) => Operation::Replace { path, value: value.clone() },
+			_ => Operation::Remove { path },
+		}
+	}
+}
+
+/// Semantic difference between two [Value](../value/enum.Value.html)s
+///
+/// Key order, whitespace, escape sequences and number formatting are ignored.
+///
+/// ```
+/// # use json_peek::util;
+/// # use json_peek::util::Span;
+/// # use json_peek::patch::{ChangeKind, Diff};
+/// let old = util::from_str(r#"{ "a": 1, "b": [true] }"#).unwrap();
+/// let new = util::from_str(r#"{"b":[true,false],"a":1.0}"#).unwrap();
+///
+/// let diff = Diff::new(&old, &new);
+/// assert_eq!(diff.changes.len(), 1);
+/// assert_eq!(diff.changes[0].kind, ChangeKind::Added);
+/// assert_eq!(diff.changes[0].old_span, Span::new(15, 21));
+/// assert_eq!(diff.changes[0].new_span, Span::new(11, 16));
+///
+/// assert_eq!(diff.to_patch().to_string(), r#"[{"op":"add","path":"/b/1","value":false}]"#);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Diff<'a> {
+	pub changes: Vec<Change<'a>>,
+}
+
+impl<'a> Diff<'a> {
+	pub fn new(old: &'a Value, new: &'a Value) -> Diff<'a> {
+		let mut diff = Diff::default();
+		diff.compare(JsonPointer::root(), old, new);
+		diff
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.changes.is_empty()
+	}
+
+	/// JSON Patch that turn the old value into the new one
+	pub fn to_patch(&self) -> Patch {
+		Patch::new(self.changes.iter().map(Change::to_operation).collect())
+	}
+
+	fn push(&mut self, kind: ChangeKind, path: JsonPointer, old: (Option<&'a Value>, Span), new: (Option<&'a Value>, Span)) {
+		self.changes.push(Change {
+			kind,
+			path,
+			old: old.0,
+			new: new.0,
+			old_span: old.1,
+			new_span: new.1,
+		});
+	}
+
+	fn compare(&mut self, path: JsonPointer, old: &'a Value, new: &'a Value) {
+		match (old, new) {
+			(Value::Object(a), Value::Object(b)) => self.object(path, a, b),
+			(Value::Array(a), Value::Array(b)) => self.array(path, a, b),
+			_ if old.semantic_eq(new) => {}
+			_ => self.push(ChangeKind::Changed, path, (Some(old), old.span()), (Some(new), new.span())),
+		}
+	}
+
+	fn object(&mut self, path: JsonPointer, old: &'a Object, new: &'a Object) {
+		let old_members = members(old);
+		let new_members = members(new);
+		let find = |members: &[(Cow<'a, str>, &'a Value)], key: &str| members.iter().find(|(x, _)| x == key).map(|(_, value)| *value);
+
+		for (key, value) in &old_members {
+			match find(&new_members, key) {
+				Some(other) => self.compare(path.join(key.as_ref()), value, other),
+				None => self.push(ChangeKind::Removed, path.join(key.as_ref()), (Some(value), value.span()), (None, new.span)),
+			}
+		}
+
+		for (key, value) in &new_members {
+			if find(&old_members, key).is_none() {
+				self.push(ChangeKind::Added, path.join(key.as_ref()), (None, old.span), (Some(value), value.span()));
+			}
+		}
+	}
+
+	fn array(&mut self, path: JsonPointer, old: &'a Array, new: &'a Array) {
+		let mut index = 0;
+		let mut removed = Vec::new();
+		let mut added = Vec::new();
+
+		// Each run of removed and added items is paired up into changes first, leftover items are then removed or added
+		for edit in edits(&old.value, &new.value).into_iter().chain(Some(Edit::Keep)) {
+			match edit {
+				Edit::Remove(x) => removed.push(&old.value[x]),
+				Edit::Add(x) => added.push(&new.value[x]),
+				Edit::Keep => {
+					let paired = removed.len().min(added.len());
+					for (before, after) in removed.iter().zip(&added) {
+						self.compare(path.join(index.to_string()), before, after);
+						index += 1;
+					}
+					for value in &removed[paired..] {
+						self.push(ChangeKind::Removed, path.join(index.to_string()), (Some(value), value.span()), (None, new.span));
+					}
+					for value in &added[paired..] {
+						self.push(ChangeKind::Added, path.join(index.to_string()), (None, old.span), (Some(value), value.span()));
+						index += 1;
+					}
+
+					removed.clear();
+					added.clear();
+					index += 1;
+				}
+			}
+		}
+	}
+}
+
+/// Object members with unescaped keys in source order
+fn members(object: &Object) -> Vec<(Cow<'_, str>, &Value)> {
+	object.iter().map(|(key, value)| (unescape(&key.value), value)).collect()
+}
+
+enum Edit {
+	Keep,
+	Remove(usize),
+	Add(usize),
+}
+
+/// Shortest edit script between two arrays using longest common subsequence
+fn edits(old: &[Value], new: &[Value]) -> Vec<Edit> {
+	let prefix = old.iter().zip(new).take_while(|(a, b)| a.semantic_eq(b)).count();
+	let suffix = old[prefix..]
+		.iter()
+		.rev()
+		.zip(new[prefix..].iter().rev())
+		.take_while(|(a, b)| a.semantic_eq(b))
+		.count();
+
+	let old_middle = &old[prefix..old.len() - suffix];
+	let new_middle = &new[prefix..new.len() - suffix];
+	let (n, m) = (old_middle.len(), new_middle.len());
+
+	let mut result: Vec<Edit> = (0..prefix).map(|_| Edit::Keep).collect();
+
+	if n.saturating_mul(m) > MAX_LCS_SIZE {
+		// Too big, just pair them up by index
+		let common = n.min(m);
+		result.extend((0..common).flat_map(|x| vec![Edit::Remove(prefix + x), Edit::Add(prefix + x)]));
+		result.extend((common..n).map(|x| Edit::Remove(prefix + x)));
+		result.extend((common..m).map(|x| Edit::Add(prefix + x)));
+	}
+	else {
+		// table[i][j] is the length of LCS between old_middle[i..] and new_middle[j..]
+		let mut table = vec![vec![0usize; m + 1]; n + 1];
+		for i in (0..n).rev() {
+			for j in (0..m).rev() {
+				table[i][j] = if old_middle[i].semantic_eq(&new_middle[j]) {
+					table[i + 1][j + 1] + 1
+				}
+				else {
+					table[i + 1][j].max(table[i][j + 1])
+				};
+			}
+		}
+
+		let (mut i, mut j) = (0, 0);
+		while i < n || j < m {
+			if i < n && j < m && old_middle[i].semantic_eq(&new_middle[j]) {
+				result.push(Edit::Keep);
+				i += 1;
+				j += 1;
+			}
+			else if j == m || (i < n && table[i + 1][j] >= table[i][j + 1]) {
+				result.push(Edit::Remove(prefix + i));
+				i += 1;
+			}
+			else {
+				result.push(Edit::Add(prefix + j));
+				j += 1;
+			}
+		}
+	}
+
+	result.extend((0..suffix).map(|_| Edit::Keep));
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::util;
+
+	fn diff_patch(old: &str, new: &str) -> String {
+		let old = util::from_str(old).unwrap();
+		let new = util::from_str(new).unwrap();
+		Diff::new(&old, &new).to_patch().to_string()
+	}
+
+	#[test]
+	fn ignore_formatting() {
+		let old = r#"{ "a": "\u0041", "b": 100.0, "c": {"x": null, "y": []} }"#;
+		let new = r#"{"c":{"y":[],"x":null},"b":100,"a":"A"}"#;
+		assert_eq!(diff_patch(old, new), "[]");
+	}
+
+	#[test]
+	fn object_changes() {
+		let old = r#"{ "keep": 1, "gone": 2, "edit": { "deep": true } }"#;
+		let new = r#"{ "keep": 1, "edit": { "deep": false }, "new~/": 3 }"#;
+
+		assert_eq!(
+			diff_patch(old, new),
+			r#"[{"op":"remove","path":"/gone"},{"op":"replace","path":"/edit/deep","value":false},{"op":"add","path":"/new~0~1","value":3}]"#
+		);
+	}
+
+	#[test]
+	fn array_changes() {
+		assert_eq!(diff_patch("[1, 2, 3]", "[0, 1, 2, 3]"), r#"[{"op":"add","path":"/0","value":0}]"#);
+		assert_eq!(diff_patch("[1, 2, 3, 4]", "[1, 4]"), r#"[{"op":"remove","path":"/1"},{"op":"remove","path":"/1"}]"#);
+		assert_eq!(
+			diff_patch("[1, 2, 3]", "[1, 5, 3, 6]"),
+			r#"[{"op":"replace","path":"/1","value":5},{"op":"add","path":"/3","value":6}]"#
+		);
+		assert_eq!(diff_patch("[]", "{}"), r#"[{"op":"replace","path":"","value":{}}]"#);
+	}
+
+	#[test]
+	fn spans_on_both_sides() {
+		let old = util::from_str(r#"{"a": [1, 2]}"#).unwrap();
+		let new = util::from_str(r#"{ "a": [1, 20] }"#).unwrap();
+		let diff = Diff::new(&old, &new);
+
+		assert_eq!(diff.changes.len(), 1);
+		assert_eq!(diff.changes[0].path.to_string(), "/a/1");
+		assert_eq!(diff.changes[0].old_span, Span::new(10, 11));
+		assert_eq!(diff.changes[0].new_span, Span::new(11, 13));
+	}
+}
@@ -0,0 +1,91 @@
+use crate::util::unescape;
+use crate::value::{Object, Value};
+use std::collections::HashMap;
+
+/// Apply JSON Merge Patch as described in [RFC 7386](https://www.rfc-editor.org/rfc/rfc7386)
+///
+/// Merge patch can't fail, anything that isn't an object simply replace the target.
+/// Merged objects are [derived](../value/enum.Origin.html#variant.Derived) while values copied from the patch keep their span into the patch document.
+///
+/// ```
+/// # use json_peek::util;
+/// # use json_peek::patch::merge_patch;
+/// let target = util::from_str(r#"{ "title": "Hello", "author": { "name": "Boomber" }, "tags": ["a"] }"#).unwrap();
+/// let patch = util::from_str(r#"{ "title": "Goodbye", "author": { "name": null }, "tags": ["b"] }"#).unwrap();
+///
+/// let result = merge_patch(&target, &patch);
+/// assert_eq!(result, util::from_str(r#"{ "title": "Goodbye", "author": {}, "tags": ["b"] }"#).unwrap());
+/// ```
+pub fn merge_patch(target: &Value, patch: &Value) -> Value {
+	let patch = match patch {
+		Value::Object(patch) => patch,
+		_ => return patch.clone(),
+	};
+
+	let mut result = match target {
+		Value::Object(target) => target.clone(),
+		_ => Object::derived(patch.span, HashMap::new()),
+	};
+	result.mark_derived();
+
+	for (key, value) in &patch.value {
+		let name = unescape(&key.value);
+		let existing = result.value.keys().find(|x| unescape(&x.value) == name).cloned();
+
+		if value.is_null() {
+			if let Some(existing) = existing {
+				result.value.remove(&existing);
+			}
+			continue;
+		}
+
+		let merged = match existing.as_ref().and_then(|x| result.value.get(x)) {
+			Some(current) => merge_patch(current, value),
+			None => merge_patch(&Value::null(), value),
+		};
+		result.value.insert(existing.unwrap_or_else(|| key.clone()), merged);
+	}
+
+	Value::Object(result)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::util::{self, Span};
+	use crate::value::{Keyable, Origin};
+
+	#[test]
+	fn rfc_examples() {
+		let cases = [
+			(r#"{"a":"b"}"#, r#"{"a":"c"}"#, r#"{"a":"c"}"#),
+			(r#"{"a":"b"}"#, r#"{"b":"c"}"#, r#"{"a":"b","b":"c"}"#),
+			(r#"{"a":"b"}"#, r#"{"a":null}"#, r#"{}"#),
+			(r#"{"a":[{"b":"c"}]}"#, r#"{"a":[1]}"#, r#"{"a":[1]}"#),
+			(r#"["a","b"]"#, r#"["c","d"]"#, r#"["c","d"]"#),
+			(r#"{"a":"foo"}"#, r#"null"#, r#"null"#),
+			(r#"{"e":null}"#, r#"{"a":1}"#, r#"{"e":null,"a":1}"#),
+			(r#"[1,2]"#, r#"{"a":"b","c":null}"#, r#"{"a":"b"}"#),
+			(r#"{}"#, r#"{"a":{"bb":{"ccc":null}}}"#, r#"{"a":{"bb":{}}}"#),
+		];
+
+		for (target, patch, expected) in cases.iter() {
+			let target = util::from_str(target).unwrap();
+			let patch = util::from_str(patch).unwrap();
+			assert_eq!(merge_patch(&target, &patch), util::from_str(expected).unwrap());
+		}
+	}
+
+	#[test]
+	fn merged_origin() {
+		let target = util::from_str(r#"{"a": {"b": 1}, "c": 2}"#).unwrap();
+		let patch = util::from_str(r#"{"a": {"d": 3}, "e": {"f": 4}}"#).unwrap();
+		let result = merge_patch(&target, &patch);
+
+		assert_eq!(result.origin(), Origin::Derived);
+		assert_eq!(result.get("a").unwrap().origin(), Origin::Derived);
+		assert_eq!(result.get("c").unwrap().source_span(), Some(Span::new(21, 22)));
+		assert_eq!(result.get("e").unwrap().origin(), Origin::Derived);
+		assert_eq!(result.get("e").unwrap().span(), Span::new(21, 29));
+	}
+}
@@ -0,0 +1,101 @@
+use crate::util::escape;
+use crate::value::{Item, Value};
+use std::fmt;
+
+mod apply;
+mod diff;
+mod merge;
+mod pointer;
+
+pub use apply::PatchError;
+pub use diff::{Change, ChangeKind, Diff};
+pub use merge::merge_patch;
+pub use pointer::{JsonPointer, PointerError};
+
+/// Single operation of a [Patch](struct.Patch.html)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation {
+	Add { path: JsonPointer, value: Value },
+	Remove { path: JsonPointer },
+	Replace { path: JsonPointer, value: Value },
+	Move { from: JsonPointer, path: JsonPointer },
+	Copy { from: JsonPointer, path: JsonPointer },
+	Test { path: JsonPointer, value: Value },
+}
+
+impl Operation {
+	/// Name of this operation as it appear in the `op` member
+	pub fn name(&self) -> &'static str {
+		match self {
+			Operation::Add { .. } => "add",
+			Operation::Remove { .. } => "remove",
+			Operation::Replace { .. } => "replace",
+			Operation::Move { .. } => "move",
+			Operation::Copy { .. } => "copy",
+			Operation::Test { .. } => "test",
+		}
+	}
+
+	/// Location that this operation target
+	pub fn path(&self) -> &JsonPointer {
+		match self {
+			Operation::Add { path, .. }
+			| Operation::Remove { path }
+			| Operation::Replace { path, .. }
+			| Operation::Move { path, .. }
+			| Operation::Copy { path, .. }
+			| Operation::Test { path, .. } => path,
+		}
+	}
+}
+
+/// Write the operation as a JSON object
+impl fmt::Display for Operation {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let pointer = |pointer: &JsonPointer| escape(&pointer.to_string()).into_owned();
+
+		write!(f, "{{\"op\":\"{}\",\"path\":\"{}\"", self.name(), pointer(self.path()))?;
+		match self {
+			Operation::Add { value, .. } | Operation::Replace { value, .. } | Operation::Test { value, .. } => {
+				write!(f, ",\"value\":{}", value)?
+			}
+			Operation::Move { from, .. } | Operation::Copy { from, .. } => write!(f, ",\"from\":\"{}\"", pointer(from))?,
+			Operation::Remove { .. } => {}
+		}
+		write!(f, "}}")
+	}
+}
+
+/// JSON Patch document as described in [RFC 6902](https://www.rfc-editor.org/rfc/rfc6902)
+///
+/// Each operation keep the span of the object it's parsed from so errors can point back into the patch document.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Patch {
+	pub operations: Vec<Item<Operation>>,
+}
+
+impl Patch {
+	/// Create a patch that doesn't come from any source
+	pub fn new(operations: Vec<Operation>) -> Patch {
+		let operations = operations.into_iter().map(Item::synthetic).collect();
+		Patch { operations }
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.operations.is_empty()
+	}
+}
+
+/// Write the patch as a JSON array
+impl fmt::Display for Patch {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "[")?;
+		for (index, operation) in self.operations.iter().enumerate() {
+			if index > 0 {
+				write!(f, ",")?;
+			}
+			write!(f, "{}", operation.value)?;
+		}
+		write!(f, "]")
+	}
+}
@@ -0,0 +1,167 @@
+use crate::util::unescape;
+use crate::value::Value;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Problem found while parsing a [JsonPointer](struct.JsonPointer.html), contain the byte offset inside of the pointer
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+pub enum PointerError {
+	/// Non-empty pointer must start with `/`
+	#[error("JSON pointer must start with '/'")]
+	MissingSlash,
+
+	/// `~` that isn't followed by `0` or `1`
+	#[error("Invalid escape sequence at {0}")]
+	InvalidEscape(usize),
+}
+
+/// JSON Pointer as described in [RFC 6901](https://www.rfc-editor.org/rfc/rfc6901), reference tokens are kept unescaped
+///
+/// ```
+/// # use json_peek::util;
+/// # use json_peek::patch::JsonPointer;
+/// let value = util::from_str(r#"{ "a/b": [0, { "c": true }] }"#).unwrap();
+/// let pointer = JsonPointer::parse("/a~1b/1/c").unwrap();
+///
+/// assert_eq!(pointer.tokens(), ["a/b", "1", "c"]);
+/// assert_eq!(value.pointer(&pointer).unwrap().to_string(), "true");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JsonPointer(Vec<String>);
+
+impl JsonPointer {
+	/// Pointer to the whole document
+	pub const fn root() -> JsonPointer {
+		JsonPointer(Vec::new())
+	}
+
+	pub fn parse(pointer: &str) -> Result<JsonPointer, PointerError> {
+		if pointer.is_empty() {
+			return Ok(JsonPointer::root());
+		}
+		if !pointer.starts_with('/') {
+			return Err(PointerError::MissingSlash);
+		}
+
+		let mut tokens = Vec::new();
+		let mut offset = 1;
+
+		for raw in pointer[1..].split('/') {
+			let mut token = String::with_capacity(raw.len());
+			let mut chars = raw.char_indices();
+
+			while let Some((index, current)) = chars.next() {
+				match current {
+					'~' => match chars.next() {
+						Some((_, '0')) => token.push('~'),
+						Some((_, '1')) => token.push('/'),
+						_ => return Err(PointerError::InvalidEscape(offset + index)),
+					},
+					_ => token.push(current),
+				}
+			}
+
+			tokens.push(token);
+			offset += raw.len() + 1;
+		}
+
+		Ok(JsonPointer(tokens))
+	}
+
+	pub fn tokens(&self) -> &[String] {
+		&self.0
+	}
+
+	pub fn is_root(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	pub fn push(&mut self, token: impl Into<String>) {
+		self.0.push(token.into());
+	}
+
+	/// Create a new pointer that point to `token` inside of this one
+	pub fn join(&self, token: impl Into<String>) -> JsonPointer {
+		let mut result = self.clone();
+		result.push(token);
+		result
+	}
+
+	/// Split this pointer into its parent and the last token, `None` if this is the root
+	pub fn split_last(&self) -> Option<(JsonPointer, &str)> {
+		let (last, parent) = self.0.split_last()?;
+		Some((JsonPointer(parent.to_vec()), last))
+	}
+}
+
+impl fmt::Display for JsonPointer {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for token in &self.0 {
+			write!(f, "/{}", token.replace('~', "~0").replace('/', "~1"))?;
+		}
+		Ok(())
+	}
+}
+
+impl FromStr for JsonPointer {
+	type Err = PointerError;
+
+	fn from_str(pointer: &str) -> Result<JsonPointer, PointerError> {
+		JsonPointer::parse(pointer)
+	}
+}
+
+/// Array index as described in RFC 6901, leading zeros aren't allowed
+pub(super) fn parse_index(token: &str) -> Option<usize> {
+	let is_valid = !token.is_empty() && token.chars().all(|x| x.is_ascii_digit()) && (token == "0" || !token.starts_with('0'));
+	if is_valid {
+		token.parse().ok()
+	}
+	else {
+		None
+	}
+}
+
+impl Value {
+	/// Get the value that `pointer` refer to
+	pub fn pointer(&self, pointer: &JsonPointer) -> Option<&Value> {
+		pointer.tokens().iter().try_fold(self, |current, token| match current {
+			Value::Object(object) => object.value.iter().find(|(key, _)| unescape(&key.value) == token.as_str()).map(|(_, value)| value),
+			Value::Array(array) => array.value.get(parse_index(token)?),
+			_ => None,
+		})
+	}
+
+	/// Get a mutable reference to the value that `pointer` refer to
+	pub fn pointer_mut(&mut self, pointer: &JsonPointer) -> Option<&mut Value> {
+		pointer.tokens().iter().try_fold(self, |current, token| match current {
+			Value::Object(object) => object.value.iter_mut().find(|(key, _)| unescape(&key.value) == token.as_str()).map(|(_, value)| value),
+			Value::Array(array) => array.value.get_mut(parse_index(token)?),
+			_ => None,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse_and_display() {
+		for pointer in ["", "/", "/foo/0", "/a~1b/m~0n", "/ /\\\""].iter() {
+			assert_eq!(JsonPointer::parse(pointer).unwrap().to_string(), *pointer);
+		}
+
+		assert_eq!(JsonPointer::parse("foo"), Err(PointerError::MissingSlash));
+		assert_eq!(JsonPointer::parse("/a/b~2"), Err(PointerError::InvalidEscape(4)));
+	}
+
+	#[test]
+	fn index() {
+		assert_eq!(parse_index("0"), Some(0));
+		assert_eq!(parse_index("12"), Some(12));
+		assert_eq!(parse_index("012"), None);
+		assert_eq!(parse_index("-"), None);
+	}
+}
@@ -0,0 +1,103 @@
+use std::borrow::Cow;
+
+/// Decode escape sequences of a JSON string, [Value](../value/enum.Value.html) keep strings exactly as they appear in the source
+///
+/// Unknown escape sequences are replaced by the escaped character and broken surrogate pairs become `U+FFFD`.
+///
+/// ```
+/// # use json_peek::util::unescape;
+/// assert_eq!(unescape(r#"a\tb\u00e9\ud83d\ude00"#), "a\tbé😀");
+/// ```
+pub fn unescape(raw: &str) -> Cow<'_, str> {
+	if !raw.contains('\\') {
+		return Cow::Borrowed(raw);
+	}
+
+	let mut result = String::with_capacity(raw.len());
+	let mut chars = raw.chars();
+
+	while let Some(current) = chars.next() {
+		if current != '\\' {
+			result.push(current);
+			continue;
+		}
+
+		match chars.next() {
+			Some('b') => result.push('\u{8}'),
+			Some('f') => result.push('\u{c}'),
+			Some('n') => result.push('\n'),
+			Some('r') => result.push('\r'),
+			Some('t') => result.push('\t'),
+			Some('u') => {
+				let hex: String = chars.by_ref().take(4).collect();
+				let high = u32::from_str_radix(&hex, 16).unwrap_or(0xFFFD);
+
+				let code = if (0xD800..0xDC00).contains(&high) {
+					let rest: String = chars.clone().take(6).collect();
+					let low = rest.strip_prefix("\\u").and_then(|x| u32::from_str_radix(x, 16).ok());
+
+					match low {
+						Some(low) if (0xDC00..0xE000).contains(&low) => {
+							chars.nth(5);
+							0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+						}
+						_ => 0xFFFD,
+					}
+				}
+				else {
+					high
+				};
+				result.push(std::char::from_u32(code).unwrap_or('\u{FFFD}'));
+			}
+			Some(other) => result.push(other),
+			None => result.push('\\'),
+		}
+	}
+
+	Cow::Owned(result)
+}
+
+/// Escape `value` so it can be written inside of a JSON string, this is the opposite of [unescape](fn.unescape.html)
+///
+/// ```
+/// # use json_peek::util::escape;
+/// assert_eq!(escape("say \"hi\"\n"), r#"say \"hi\"\n"#);
+/// ```
+pub fn escape(value: &str) -> Cow<'_, str> {
+	if !value.chars().any(|x| x == '"' || x == '\\' || x < ' ') {
+		return Cow::Borrowed(value);
+	}
+
+	let mut result = String::with_capacity(value.len() + 2);
+	for current in value.chars() {
+		match current {
+			'"' => result.push_str("\\\""),
+			'\\' => result.push_str("\\\\"),
+			'\n' => result.push_str("\\n"),
+			'\r' => result.push_str("\\r"),
+			'\t' => result.push_str("\\t"),
+			'\u{8}' => result.push_str("\\b"),
+			'\u{c}' => result.push_str("\\f"),
+			_ if current < ' ' => result.push_str(&format!("\\u{:04x}", current as u32)),
+			_ => result.push(current),
+		}
+	}
+
+	Cow::Owned(result)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn round_trip() {
+		let value = "quote \" backslash \\ control \u{1} emoji 😀";
+		assert_eq!(unescape(&escape(value)), value);
+	}
+
+	#[test]
+	fn invalid_escape() {
+		assert_eq!(unescape(r"\q \ud800"), "q \u{FFFD}");
+	}
+}
@@ -1,5 +1,9 @@
 use crate::parser::{ParseResult, Parser};
+mod escape;
+mod source;
 mod span;
+pub use escape::{escape, unescape};
+pub use source::{FileId, FileSpan, InFile, Location, SourceDb};
 pub use span::Span;
 
 /// Parse string to JSON tree
@@ -40,4 +44,44 @@ mod tests {
 			])
 		);
 	}
+
+	mod roundtrip {
+		use crate::util::escape;
+		use crate::value::{Literal, Value};
+		use proptest::prelude::*;
+
+		fn arb_value() -> impl Strategy<Value = Value> {
+			// Strings are kept as they appear in the source so they have to be escaped first
+			let leaf = prop_oneof![
+				"(?s).{0,8}".prop_map(|x| Value::test_string(&escape(&x))),
+				"-?(0|[1-9][0-9]{0,5})(\\.[0-9]{1,3})?".prop_map(|x| Value::test_number(&x)),
+				any::<bool>().prop_map(Value::test_bool),
+				Just(Value::test_null()),
+			];
+
+			leaf.prop_recursive(4, 32, 6, |inner| {
+				prop_oneof![
+					prop::collection::vec(inner.clone(), 0..6).prop_map(Value::test_array),
+					prop::collection::hash_map("[a-z_\"\\\\]{1,6}".prop_map(|x| Literal::new_literal(escape(&x))), inner, 0..6)
+						.prop_map(Value::test_object),
+				]
+			})
+		}
+
+		proptest! {
+			#[test]
+			fn serialize_then_parse(value in arb_value()) {
+				let content = value.to_string();
+				let result = super::super::from_str(&content).expect("Invalid JSON");
+
+				prop_assert_eq!(&result, &value);
+				prop_assert_eq!(result.validate_spans(&content), Ok(()));
+			}
+
+			#[test]
+			fn parse_arbitrary_input(content in "\\PC{0,64}") {
+				let _ = super::super::from_str(&content);
+			}
+		}
+	}
 }
@@ -0,0 +1,227 @@
+use super::Span;
+use crate::parser::{ParseError, Parser};
+use crate::value::Value;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Handle to a file stored inside of [SourceDb](struct.SourceDb.html)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FileId(usize);
+
+/// A [Span](struct.Span.html) that also know which file it belong to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FileSpan {
+	pub file: FileId,
+	pub span: Span,
+}
+
+impl FileSpan {
+	pub const fn new(file: FileId, span: Span) -> FileSpan {
+		FileSpan { file, span }
+	}
+}
+
+impl fmt::Display for FileSpan {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "#{}:{}", self.file.0, self.span)
+	}
+}
+
+/// Anything (usually [Value](../value/enum.Value.html) or [ParseError](../parser/enum.ParseError.html)) that came from a file inside of [SourceDb](struct.SourceDb.html)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InFile<T> {
+	pub file: FileId,
+	pub value: T,
+}
+
+impl<T> InFile<T> {
+	pub const fn new(file: FileId, value: T) -> InFile<T> {
+		InFile { file, value }
+	}
+
+	/// Attach this file to `span`, useful for spans of the nodes inside of `value`
+	pub const fn span_of(&self, span: Span) -> FileSpan {
+		FileSpan::new(self.file, span)
+	}
+
+	/// Transform the inner value while staying in the same file
+	pub fn map<U>(self, f: impl FnOnce(T) -> U) -> InFile<U> {
+		InFile::new(self.file, f(self.value))
+	}
+}
+
+impl InFile<Value> {
+	pub fn file_span(&self) -> FileSpan {
+		self.span_of(self.value.span())
+	}
+}
+
+impl InFile<ParseError<'_>> {
+	pub fn file_span(&self) -> FileSpan {
+		self.span_of(self.value.span())
+	}
+}
+
+impl<T: fmt::Display> fmt::Display for InFile<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.value.fmt(f)
+	}
+}
+
+/// Human readable position of a [FileSpan](struct.FileSpan.html), `line` and `column` start at 1
+///
+/// `column` is counted in `char` rather than byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location<'a> {
+	pub path: &'a Path,
+	pub line: usize,
+	pub column: usize,
+}
+
+impl fmt::Display for Location<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}:{}:{}", self.path.display(), self.line, self.column)
+	}
+}
+
+#[derive(Debug, Clone)]
+struct SourceFile {
+	path: PathBuf,
+	source: String,
+	line_starts: Vec<usize>,
+}
+
+impl SourceFile {
+	fn new(path: PathBuf, source: String) -> SourceFile {
+		let line_starts = std::iter::once(0)
+			.chain(source.match_indices('\n').map(|(index, _)| index + 1))
+			.collect();
+
+		SourceFile {
+			path,
+			source,
+			line_starts,
+		}
+	}
+
+	fn line_column(&self, offset: usize) -> Option<(usize, usize)> {
+		let line = match self.line_starts.binary_search(&offset) {
+			Ok(line) => line,
+			Err(line) => line - 1,
+		};
+
+		let start = self.line_starts[line];
+		let column = self.source.get(start..offset)?.chars().count();
+		Some((line + 1, column + 1))
+	}
+}
+
+/// Collection of source files that can be parsed and refer to each other by [FileId](struct.FileId.html)
+///
+/// ```
+/// # use json_peek::util::SourceDb;
+/// # use json_peek::value::prelude::*;
+/// let mut db = SourceDb::new();
+/// let file = db.add("data/foo/advancements/bar.json", "{\n  \"parent\": 42\n}");
+///
+/// let value = db.parse(file).unwrap().unwrap();
+/// let parent = value.value.get("parent").unwrap();
+///
+/// let location = db.location(value.span_of(parent.span())).unwrap();
+/// assert_eq!(location.to_string(), "data/foo/advancements/bar.json:2:13");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SourceDb {
+	files: Vec<SourceFile>,
+}
+
+impl SourceDb {
+	pub fn new() -> SourceDb {
+		SourceDb::default()
+	}
+
+	/// Add `source` to the database under the name `path`, the file doesn't have to exist on disk
+	pub fn add(&mut self, path: impl Into<PathBuf>, source: impl Into<String>) -> FileId {
+		let id = FileId(self.files.len());
+		self.files.push(SourceFile::new(path.into(), source.into()));
+		id
+	}
+
+	/// Read the file at `path` and add it to the database
+	pub fn load(&mut self, path: impl AsRef<Path>) -> io::Result<FileId> {
+		let path = path.as_ref();
+		let source = fs::read_to_string(path)?;
+		Ok(self.add(path, source))
+	}
+
+	/// Iterate through every file in this database
+	pub fn files(&self) -> impl Iterator<Item = FileId> {
+		(0..self.files.len()).map(FileId)
+	}
+
+	pub fn path(&self, file: FileId) -> Option<&Path> {
+		self.files.get(file.0).map(|x| x.path.as_path())
+	}
+
+	pub fn source(&self, file: FileId) -> Option<&str> {
+		self.files.get(file.0).map(|x| x.source.as_str())
+	}
+
+	/// Parse `file`, the result and its error both remember which file they came from
+	///
+	/// `None` if `file` doesn't belong to this database
+	pub fn parse(&self, file: FileId) -> Option<Result<InFile<Value>, InFile<ParseError<'_>>>> {
+		let result = Parser::new(self.source(file)?)
+			.parse()
+			.map(|value| InFile::new(file, value))
+			.map_err(|error| InFile::new(file, error));
+		Some(result)
+	}
+
+	/// Get the 1-based line and column of `offset` inside of `file`
+	pub fn line_column(&self, file: FileId, offset: usize) -> Option<(usize, usize)> {
+		self.files.get(file.0)?.line_column(offset)
+	}
+
+	/// Resolve the start of `span` into path, line and column
+	pub fn location(&self, span: FileSpan) -> Option<Location<'_>> {
+		let path = self.path(span.file)?;
+		let (line, column) = self.line_column(span.file, span.span.start)?;
+
+		Some(Location { path, line, column })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn line_column() {
+		let mut db = SourceDb::new();
+		let file = db.add("foo.json", "[\n\t\"ใ\", 1,\n2]");
+
+		assert_eq!(db.line_column(file, 0), Some((1, 1)));
+		assert_eq!(db.line_column(file, 2), Some((2, 1)));
+		assert_eq!(db.line_column(file, 9), Some((2, 6)));
+		assert_eq!(db.line_column(file, 13), Some((3, 1)));
+		assert_eq!(db.line_column(file, 5), None);
+	}
+
+	#[test]
+	fn parse_many_files() {
+		let mut db = SourceDb::new();
+		let foo = db.add("foo.json", "[1, 2]");
+		let bar = db.add("bar.json", "{\n\"a\": [}");
+
+		assert_eq!(db.files().collect::<Vec<_>>(), vec![foo, bar]);
+		assert_eq!(db.parse(foo).unwrap().unwrap().file_span(), FileSpan::new(foo, Span::new(0, 6)));
+		assert!(SourceDb::new().parse(foo).is_none());
+
+		let error = db.parse(bar).unwrap().unwrap_err();
+		assert_eq!(error.file_span(), FileSpan::new(bar, Span::new(8, 9)));
+		assert_eq!(db.location(error.file_span()).unwrap().to_string(), "bar.json:2:7");
+	}
+}
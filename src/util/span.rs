@@ -1,16 +1,24 @@
 use std::{
 	fmt,
-	ops::{Add, AddAssign, RangeInclusive, Sub, SubAssign},
+	ops::{Add, AddAssign, Range, RangeInclusive, Sub, SubAssign},
 };
 
 /// General position information of string
 ///
+/// Span is a byte offset into the source with an *exclusive* `end`, just like `Range<usize>`.
+/// So `&source[span.range()]` is exactly the text that the span is pointing at
+/// and an empty span is a point in between two characters.
+///
+/// Every [Value](../value/enum.Value.html) produced by the parser follow the same rule:
+/// strings include their quotes and objects/arrays start at the opening bracket and end after the closing bracket.
+///
 /// ```
 /// # use json_peek::util::Span;
 /// let a = Span::new(1, 5);
-/// let b = Span::from(1..=5);
+/// let b = Span::from(1..5);
 ///
 /// assert_eq!(a, b);
+/// assert_eq!(&"megumin"[a.range()], "egum");
 /// ```
 ///
 /// ```
@@ -30,8 +38,8 @@ impl Span {
 	}
 
 	/// Get the range of this Span
-	pub const fn range(&self) -> RangeInclusive<usize> {
-		self.start..=self.end
+	pub const fn range(&self) -> Range<usize> {
+		self.start..self.end
 	}
 
 	/// Create a point out of the start position of this Span
@@ -55,6 +63,119 @@ impl Span {
 		self.start == self.end
 	}
 
+	/// Same as [is_point()](#method.is_point)
+	pub const fn is_empty(&self) -> bool {
+		self.is_point()
+	}
+
+	/// Length of this Span, inverted Span has a length of 0
+	///
+	/// ```
+	/// # use json_peek::util::Span;
+	/// assert_eq!(Span::new(3, 7).len(), 4);
+	/// assert_eq!(Span::new(7, 3).len(), 0);
+	/// ```
+	pub const fn len(&self) -> usize {
+		self.end.saturating_sub(self.start)
+	}
+
+	/// Check if `offset` is inside of this Span, the end is exclusive
+	///
+	/// ```
+	/// # use json_peek::util::Span;
+	/// let span = Span::new(3, 7);
+	///
+	/// assert!(span.contains(3));
+	/// assert!(!span.contains(7));
+	/// ```
+	pub const fn contains(&self, offset: usize) -> bool {
+		self.start <= offset && offset < self.end
+	}
+
+	/// Check if `other` is entirely inside of this Span
+	///
+	/// ```
+	/// # use json_peek::util::Span;
+	/// let span = Span::new(3, 7);
+	///
+	/// assert!(span.contains_span(Span::new(3, 7)));
+	/// assert!(span.contains_span(Span::new(7, 7)));
+	/// assert!(!span.contains_span(Span::new(5, 8)));
+	/// ```
+	pub const fn contains_span(&self, other: Span) -> bool {
+		self.start <= other.start && other.end <= self.end
+	}
+
+	/// Get the part that both Span overlap, two Spans that only touch each other result in a point
+	///
+	/// ```
+	/// # use json_peek::util::Span;
+	/// let span = Span::new(3, 7);
+	///
+	/// assert_eq!(span.intersect(Span::new(5, 10)), Some(Span::new(5, 7)));
+	/// assert_eq!(span.intersect(Span::new(7, 10)), Some(Span::new(7, 7)));
+	/// assert_eq!(span.intersect(Span::new(8, 10)), None);
+	/// ```
+	pub fn intersect(&self, other: Span) -> Option<Span> {
+		let start = self.start.max(other.start);
+		let end = self.end.min(other.end);
+
+		if start <= end {
+			Some(Span::new(start, end))
+		} else {
+			None
+		}
+	}
+
+	/// Smallest Span that cover both Spans, same as [from_span()](#method.from_span)
+	///
+	/// ```
+	/// # use json_peek::util::Span;
+	/// assert_eq!(Span::new(3, 5).union(Span::new(8, 10)), Span::new(3, 10));
+	/// ```
+	pub fn union(&self, other: Span) -> Span {
+		Span::from_span(*self, other)
+	}
+
+	/// Move the whole Span by `by` bytes, stop at `0` or `usize::MAX` instead of overflowing
+	///
+	/// The length is always kept but the Span might not move as far as asked,
+	/// use [checked_shift()](#method.checked_shift) if that matter.
+	///
+	/// ```
+	/// # use json_peek::util::Span;
+	/// assert_eq!(Span::new(3, 5).shift(2), Span::new(5, 7));
+	/// assert_eq!(Span::new(3, 5).shift(-4), Span::new(0, 2));
+	/// ```
+	pub fn shift(&self, by: isize) -> Span {
+		self.checked_shift(by).unwrap_or_else(|| {
+			let len = self.len();
+			if by < 0 {
+				Span::new(0, len)
+			} else {
+				Span::new(usize::MAX - len, usize::MAX)
+			}
+		})
+	}
+
+	/// Move the whole Span by `by` bytes, return `None` if that would underflow or overflow
+	///
+	/// ```
+	/// # use json_peek::util::Span;
+	/// assert_eq!(Span::new(3, 5).checked_shift(-3), Some(Span::new(0, 2)));
+	/// assert_eq!(Span::new(3, 5).checked_shift(-4), None);
+	/// ```
+	pub fn checked_shift(&self, by: isize) -> Option<Span> {
+		let move_by = |x: usize| {
+			if by < 0 {
+				x.checked_sub(by.unsigned_abs())
+			} else {
+				x.checked_add(by as usize)
+			}
+		};
+		Some(Span::new(move_by(self.start)?, move_by(self.end)?))
+	}
+
 	/// Shrink the size of Span by some `offset` from both size
 	///
 	/// **Does not check for overflow**
@@ -76,6 +197,75 @@ impl Span {
 		Span::new(self.start + offset, self.end - offset)
 	}
 
+	/// Same as [trim()](#method.trim) but return `None` instead of overflowing or creating an inverted Span
+	///
+	/// ```
+	/// # use json_peek::util::Span;
+	/// let span = Span::new(2, 6);
+	///
+	/// assert_eq!(span.checked_trim(2), Some(Span::new(4, 4)));
+	/// assert_eq!(span.checked_trim(3), None);
+	/// ```
+	pub fn checked_trim(&self, offset: usize) -> Option<Span> {
+		let start = self.start.checked_add(offset)?;
+		let end = self.end.checked_sub(offset)?;
+
+		if start <= end {
+			Some(Span::new(start, end))
+		} else {
+			None
+		}
+	}
+
+	/// Same as [trim()](#method.trim) but shrink into a point in the middle instead of overflowing
+	///
+	/// ```
+	/// # use json_peek::util::Span;
+	/// let span = Span::new(2, 6);
+	///
+	/// assert_eq!(span.saturating_trim(1), Span::new(3, 5));
+	/// assert_eq!(span.saturating_trim(10), Span::new(4, 4));
+	/// ```
+	pub fn saturating_trim(&self, offset: usize) -> Span {
+		self.checked_trim(offset).unwrap_or_else(|| {
+			let middle = self.start + self.len() / 2;
+			Span::new(middle, middle)
+		})
+	}
+
+	/// Shrink the end of Span by `rhs`, return `None` if that would move the end before the start
+	///
+	/// ```
+	/// # use json_peek::util::Span;
+	/// assert_eq!(Span::new(2, 6).checked_sub(4), Some(Span::new(2, 2)));
+	/// assert_eq!(Span::new(2, 6).checked_sub(5), None);
+	/// ```
+	pub fn checked_sub(&self, rhs: usize) -> Option<Span> {
+		let end = self.end.checked_sub(rhs)?;
+
+		if self.start <= end {
+			Some(Span::new(self.start, end))
+		} else {
+			None
+		}
+	}
+
+	/// Shrink the end of Span by `rhs` but never past the start
+	///
+	/// ```
+	/// # use json_peek::util::Span;
+	/// assert_eq!(Span::new(2, 6).saturating_sub(10), Span::new(2, 2));
+	/// ```
+	pub fn saturating_sub(&self, rhs: usize) -> Span {
+		self.checked_sub(rhs).unwrap_or_else(|| self.start_point())
+	}
+
+	/// Extend the end of Span by `rhs`, return `None` on overflow
+	pub fn checked_add(&self, rhs: usize) -> Option<Span> {
+		let end = self.end.checked_add(rhs)?;
+		Some(Span::new(self.start, end))
+	}
+
 	/// Create Span from two other Spans
 	/// This function will construct the biggest possible Span
 	/// 
@@ -132,6 +322,8 @@ impl SubAssign<usize> for Span {
 	}
 }
 
+/// Add each side of the Span together, this is **not** a union.
+/// See [union()](struct.Span.html#method.union) or [shift()](struct.Span.html#method.shift) instead.
 impl Add<Span> for Span {
 	type Output = Span;
 
@@ -173,9 +365,21 @@ impl fmt::Display for Span {
 	}
 }
 
+impl From<Range<usize>> for Span {
+	fn from(range: Range<usize>) -> Span {
+		Span::new(range.start, range.end)
+	}
+}
+
+impl From<Span> for Range<usize> {
+	fn from(span: Span) -> Range<usize> {
+		span.range()
+	}
+}
+
 impl From<RangeInclusive<usize>> for Span {
 	fn from(range: RangeInclusive<usize>) -> Span {
-		Span::new(*range.start(), *range.end())
+		Span::new(*range.start(), *range.end() + 1)
 	}
 }
 
@@ -185,7 +389,12 @@ mod tests {
 
 	#[test]
 	fn get_range() {
-		assert_eq!(Span::new(3, 9).range(), 3..=9);
+		assert_eq!(Span::new(3, 9).range(), 3..9);
+	}
+
+	#[test]
+	fn from_inclusive_range() {
+		assert_eq!(Span::from(3..=9), Span::new(3, 10));
 	}
 
 	#[test]
@@ -213,4 +422,23 @@ mod tests {
 			Span { start: 2, end: 6 }
 		);
 	}
+
+	#[test]
+	fn checked_arithmetic() {
+		assert_eq!(Span::new(0, 4).checked_trim(5), None);
+		assert_eq!(Span::new(0, 4).saturating_trim(5), Span::new(2, 2));
+		assert_eq!(Span::new(0, 10).checked_sub(11), None);
+		assert_eq!(Span::new(0, 10).checked_add(usize::MAX), None);
+		assert_eq!(Span::new(0, usize::MAX).shift(1), Span::new(0, usize::MAX));
+		assert_eq!(Span::new(10, 20).shift(isize::MAX).shift(isize::MAX).len(), 10);
+		assert_eq!(Span::new(0, usize::MAX).checked_shift(1), None);
+	}
+
+	#[test]
+	fn range_conversion() {
+		let range: Range<usize> = Span::new(3, 9).into();
+
+		assert_eq!(range, 3..9);
+		assert_eq!(Span::from(range), Span::new(3, 9));
+	}
 }
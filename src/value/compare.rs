@@ -0,0 +1,337 @@
+use super::{Literal, Object, Value};
+use crate::patch::JsonPointer;
+use crate::util::{unescape, Span};
+use std::fmt;
+
+/// How two [Value](enum.Value.html)s are compared by [Value::compare()](enum.Value.html#method.compare)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Comparison {
+	/// See [Value::semantic_eq()](enum.Value.html#method.semantic_eq)
+	Semantic,
+	/// See [Value::strict_eq()](enum.Value.html#method.strict_eq)
+	Strict,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MismatchKind {
+	/// Values are different or one of them is missing
+	Value,
+	/// Values are the same but their spans aren't, only reported by strict comparison
+	Span,
+	/// Object member has its key at a different span, only reported by strict comparison
+	KeySpan,
+}
+
+/// Single difference found by [Value::compare()](enum.Value.html#method.compare)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch<'a> {
+	pub kind: MismatchKind,
+	pub path: JsonPointer,
+	/// Value on the left side, `None` if it's missing
+	pub left: Option<&'a Value>,
+	/// Value on the right side, `None` if it's missing
+	pub right: Option<&'a Value>,
+	/// Span of the left value or key, for missing value this is the span of its container
+	pub left_span: Span,
+	/// Span of the right value or key, for missing value this is the span of its container
+	pub right_span: Span,
+}
+
+impl fmt::Display for Mismatch<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let side = |value: Option<&Value>| match value {
+			Some(value) => value.to_string(),
+			None => "(missing)".to_owned(),
+		};
+
+		write!(f, "{:?}: ", self.path.to_string())?;
+		match self.kind {
+			MismatchKind::Value => write!(
+				f,
+				"{} at {} != {} at {}",
+				side(self.left),
+				self.left_span,
+				side(self.right),
+				self.right_span
+			),
+			MismatchKind::Span => write!(f, "{} at {} != {}", side(self.left), self.left_span, self.right_span),
+			MismatchKind::KeySpan => write!(f, "key at {} != {}", self.left_span, self.right_span),
+		}
+	}
+}
+
+impl Value {
+	/// Compare the meaning of two values, spans, key order, escape sequences and number formatting are ignored
+	///
+	/// Integers are compared digit by digit so they don't lose precision, only fractions and exponents are compared as `f64`.
+	///
+	/// ```
+	/// # use json_peek::util;
+	/// let left = util::from_str(r#"{ "a": 1, "b": "A" }"#).unwrap();
+	/// let right = util::from_str(r#"{"b":"A","a":1.0}"#).unwrap();
+	///
+	/// assert!(left.semantic_eq(&right));
+	/// assert!(left != right);
+	/// ```
+	pub fn semantic_eq(&self, other: &Value) -> bool {
+		match (self, other) {
+			(Value::Object(a), Value::Object(b)) => {
+				a.value.len() == b.value.len()
+					&& a.value.iter().all(|(key, a)| {
+						matches!(b.member(&unescape(&key.value)), Some((_, b)) if a.semantic_eq(b))
+					})
+			}
+			(Value::Array(a), Value::Array(b)) => {
+				a.value.len() == b.value.len() && a.value.iter().zip(&b.value).all(|(a, b)| a.semantic_eq(b))
+			}
+			(Value::Number(a), Value::Number(b)) => number_eq(&a.value, &b.value),
+			(Value::Literal(a), Value::Literal(b)) => unescape(&a.value) == unescape(&b.value),
+			(Value::Bool(a), Value::Bool(b)) => a.value == b.value,
+			(Value::Null(_), Value::Null(_)) => true,
+			_ => false,
+		}
+	}
+
+	/// Same as `==` but every span, including the span of object keys, has to match as well
+	///
+	/// ```
+	/// # use json_peek::util;
+	/// let left = util::from_str("[1, 2]").unwrap();
+	/// let right = util::from_str("[1,2]").unwrap();
+	///
+	/// assert!(left == right);
+	/// assert!(!left.strict_eq(&right));
+	/// ```
+	pub fn strict_eq(&self, other: &Value) -> bool {
+		if self.span() != other.span() {
+			return false;
+		}
+
+		match (self, other) {
+			(Value::Object(a), Value::Object(b)) => {
+				a.value.len() == b.value.len()
+					&& a.value.iter().all(|(key, a)| {
+						matches!(b.value.get_key_value(key), Some((other, b)) if key.span == other.span && a.strict_eq(b))
+					})
+			}
+			(Value::Array(a), Value::Array(b)) => {
+				a.value.len() == b.value.len() && a.value.iter().zip(&b.value).all(|(a, b)| a.strict_eq(b))
+			}
+			_ => self == other,
+		}
+	}
+
+	/// Walk both trees and collect every difference along with its path, this is what [assert_json_eq!](../macro.assert_json_eq.html) print
+	///
+	/// ```
+	/// # use json_peek::util;
+	/// # use json_peek::value::{Comparison, MismatchKind};
+	/// let left = util::from_str(r#"{ "a": [1, 2], "b": true }"#).unwrap();
+	/// let right = util::from_str(r#"{ "a": [1, 3] }"#).unwrap();
+	///
+	/// let mismatches = left.compare(&right, Comparison::Semantic);
+	/// assert_eq!(mismatches.len(), 2);
+	/// assert_eq!(mismatches[0].to_string(), r#""/a/1": 2 at 11..12 != 3 at 11..12"#);
+	/// assert_eq!(mismatches[1].to_string(), r#""/b": true at 20..24 != (missing) at 0..15"#);
+	/// ```
+	pub fn compare<'a>(&'a self, other: &'a Value, comparison: Comparison) -> Vec<Mismatch<'a>> {
+		let mut comparer = Comparer {
+			comparison,
+			mismatches: Vec::new(),
+		};
+		comparer.compare(JsonPointer::root(), self, other);
+		comparer.mismatches
+	}
+}
+
+struct Comparer<'a> {
+	comparison: Comparison,
+	mismatches: Vec<Mismatch<'a>>,
+}
+
+impl<'a> Comparer<'a> {
+	fn push(&mut self, kind: MismatchKind, path: JsonPointer, left: (Option<&'a Value>, Span), right: (Option<&'a Value>, Span)) {
+		self.mismatches.push(Mismatch {
+			kind,
+			path,
+			left: left.0,
+			right: right.0,
+			left_span: left.1,
+			right_span: right.1,
+		});
+	}
+
+	fn compare(&mut self, path: JsonPointer, left: &'a Value, right: &'a Value) {
+		let is_strict = self.comparison == Comparison::Strict;
+
+		match (left, right) {
+			(Value::Object(a), Value::Object(b)) => {
+				if is_strict && a.span != b.span {
+					self.push(MismatchKind::Span, path.clone(), (Some(left), a.span), (Some(right), b.span));
+				}
+				self.object(path, a, b);
+			}
+			(Value::Array(a), Value::Array(b)) => {
+				if is_strict && a.span != b.span {
+					self.push(MismatchKind::Span, path.clone(), (Some(left), a.span), (Some(right), b.span));
+				}
+				for (index, (x, y)) in a.value.iter().zip(&b.value).enumerate() {
+					self.compare(path.join(index.to_string()), x, y);
+				}
+				for (index, x) in a.value.iter().enumerate().skip(b.value.len()) {
+					self.push(MismatchKind::Value, path.join(index.to_string()), (Some(x), x.span()), (None, b.span));
+				}
+				for (index, y) in b.value.iter().enumerate().skip(a.value.len()) {
+					self.push(MismatchKind::Value, path.join(index.to_string()), (None, a.span), (Some(y), y.span()));
+				}
+			}
+			_ if !is_strict && left.semantic_eq(right) => {}
+			_ if is_strict && left == right => {
+				if left.span() != right.span() {
+					self.push(MismatchKind::Span, path, (Some(left), left.span()), (Some(right), right.span()));
+				}
+			}
+			_ => self.push(MismatchKind::Value, path, (Some(left), left.span()), (Some(right), right.span())),
+		}
+	}
+
+	fn object(&mut self, path: JsonPointer, left: &'a Object, right: &'a Object) {
+		for (key, value) in left.iter() {
+			let name = unescape(&key.value);
+			match self.member(right, key) {
+				Some((other, x)) => {
+					if self.comparison == Comparison::Strict && key.span != other.span {
+						self.push(MismatchKind::KeySpan, path.join(name.as_ref()), (Some(value), key.span), (Some(x), other.span));
+					}
+					self.compare(path.join(name.as_ref()), value, x);
+				}
+				None => self.push(MismatchKind::Value, path.join(name.as_ref()), (Some(value), value.span()), (None, right.span)),
+			}
+		}
+
+		for (key, value) in right.iter() {
+			if self.member(left, key).is_none() {
+				self.push(MismatchKind::Value, path.join(unescape(&key.value).as_ref()), (None, left.span), (Some(value), value.span()));
+			}
+		}
+	}
+
+	fn member(&self, object: &'a Object, key: &Literal) -> Option<(&'a Literal, &'a Value)> {
+		match self.comparison {
+			Comparison::Semantic => object.member(&unescape(&key.value)),
+			Comparison::Strict => object.value.get_key_value(key),
+		}
+	}
+}
+
+/// Compare two number literals, see [Value::semantic_eq()](enum.Value.html#method.semantic_eq)
+fn number_eq(a: &str, b: &str) -> bool {
+	if let (Some(x), Some(y)) = (integer(a), integer(b)) {
+		return x == y;
+	}
+
+	match (a.parse::<f64>(), b.parse::<f64>()) {
+		(Ok(x), Ok(y)) => x == y,
+		_ => a == b,
+	}
+}
+
+/// Split an integer literal into its sign and digits without leading zeros, `-0` is the same as `0`
+fn integer(text: &str) -> Option<(bool, &str)> {
+	let (is_negative, digits) = match text.strip_prefix('-') {
+		Some(digits) => (true, digits),
+		None => (false, text),
+	};
+	if digits.is_empty() || !digits.bytes().all(|x| x.is_ascii_digit()) {
+		return None;
+	}
+
+	let digits = digits.trim_start_matches('0');
+	Some((is_negative && !digits.is_empty(), digits))
+}
+
+/// Assert that two [Value](value/enum.Value.html)s are equal, semantically by default or with
+/// [Comparison::Strict](value/enum.Comparison.html) as the third argument.
+/// On failure every mismatch is printed along with its JSON Pointer path.
+///
+/// ```
+/// # use json_peek::{assert_json_eq, util};
+/// # use json_peek::value::Comparison;
+/// let left = util::from_str(r#"{ "a": [1, 2.0] }"#).unwrap();
+/// let right = util::from_str(r#"{ "a": [1.0, 2] }"#).unwrap();
+///
+/// assert_json_eq!(left, right);
+/// assert_json_eq!(left, left.clone(), Comparison::Strict);
+/// ```
+#[macro_export]
+macro_rules! assert_json_eq {
+	($left:expr, $right:expr $(,)?) => {
+		$crate::assert_json_eq!($left, $right, $crate::value::Comparison::Semantic)
+	};
+	($left:expr, $right:expr, $comparison:expr $(,)?) => {
+		match (&$left, &$right, $comparison) {
+			(left, right, comparison) => {
+				let mismatches = $crate::value::Value::compare(left, right, comparison);
+				if !mismatches.is_empty() {
+					let tree: String = mismatches.iter().map(|x| format!("\n    {}", x)).collect();
+					panic!("assertion failed: `left == right` ({:?}){}", comparison, tree);
+				}
+			}
+		}
+	};
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::util;
+
+	#[test]
+	fn strict_mismatches() {
+		let left = util::from_str(r#"{"a": [1], "b": null}"#).unwrap();
+		let right = util::from_str(r#"{"b": null, "a": [1]}"#).unwrap();
+
+		assert!(left.semantic_eq(&right));
+		assert!(left.compare(&right, Comparison::Semantic).is_empty());
+
+		let kinds: Vec<_> = left.compare(&right, Comparison::Strict).iter().map(|x| (x.kind, x.path.to_string())).collect();
+		assert_eq!(
+			kinds,
+			[
+				(MismatchKind::KeySpan, "/a".to_owned()),
+				(MismatchKind::Span, "/a".to_owned()),
+				(MismatchKind::Span, "/a/0".to_owned()),
+				(MismatchKind::KeySpan, "/b".to_owned()),
+				(MismatchKind::Span, "/b".to_owned()),
+			]
+		);
+	}
+
+	#[test]
+	fn numbers_and_escapes() {
+		let left = util::from_str(r#"["é", 10, -0.5]"#).unwrap();
+		let right = util::from_str(r#"["\u00e9", 10.00, -0.50]"#).unwrap();
+
+		assert!(left.semantic_eq(&right));
+		assert!(!left.semantic_eq(&util::from_str(r#"["é", 10, -0.5, 1]"#).unwrap()));
+		assert!(left.strict_eq(&left.clone()));
+	}
+
+	#[test]
+	fn large_integers() {
+		let left = util::from_str("[9007199254740993, -0, 100000000000000000000000001]").unwrap();
+
+		assert!(left.semantic_eq(&util::from_str("[9007199254740993, 0, 100000000000000000000000001]").unwrap()));
+		assert!(!left.semantic_eq(&util::from_str("[9007199254740992, 0, 100000000000000000000000001]").unwrap()));
+		assert!(!left.semantic_eq(&util::from_str("[9007199254740993, 0, 100000000000000000000000000]").unwrap()));
+		assert!(util::from_str("[1e2, 1]").unwrap().semantic_eq(&util::from_str("[100, 1.0]").unwrap()));
+	}
+
+	#[test]
+	#[should_panic(expected = r#""/a/0": 1 at 7..8 != 2 at 7..8"#)]
+	fn assert_macro() {
+		let left = util::from_str(r#"{"a": [1]}"#).unwrap();
+		let right = util::from_str(r#"{"a": [2]}"#).unwrap();
+		assert_json_eq!(left, right);
+	}
+}
@@ -0,0 +1,286 @@
+use super::{Array, Bool, Literal, Number, Object, Value};
+use crate::util::{escape, unescape, Span};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use thiserror::Error;
+
+/// Problem found while converting a [Value](enum.Value.html) into a Rust type
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+pub enum ConversionError {
+	/// Contain the expected type and the type that was found
+	#[error("Expected {1} but found {2} at {0}")]
+	InvalidType(Span, &'static str, &'static str),
+
+	/// Number can't be represented by the target type, either it's out of range or it has a fraction
+	#[error("Number {1} doesn't fit in {2} at {0}")]
+	InvalidNumber(Span, String, &'static str),
+
+	/// Object doesn't have a required field, span point at the object
+	#[error("Missing field {1:?} at {0}")]
+	MissingField(Span, &'static str),
+
+	/// Object has a field that isn't expected, span point at the key
+	#[error("Unknown field {1:?} at {0}")]
+	UnknownField(Span, String),
+
+	/// Enum tag doesn't match any of the variants
+	#[error("Unknown variant {1:?} at {0}")]
+	UnknownVariant(Span, String),
+
+	/// None of the variants of an untagged enum can be extracted, contain the name of the enum and the errors of every variant in order
+	#[error("Value doesn't match any variant of {1} at {0}")]
+	NoMatchingVariant(Span, &'static str, Vec<ConversionError>),
+}
+
+impl ConversionError {
+	pub fn span(&self) -> Span {
+		match self {
+			ConversionError::InvalidType(span, _, _)
+			| ConversionError::InvalidNumber(span, _, _)
+			| ConversionError::MissingField(span, _)
+			| ConversionError::UnknownField(span, _)
+			| ConversionError::UnknownVariant(span, _)
+			| ConversionError::NoMatchingVariant(span, _, _) => *span,
+		}
+	}
+}
+
+/// Rust string become a JSON string, it's escaped since [Value](enum.Value.html) keep strings as they appear in the source
+///
+/// ```
+/// # use json_peek::value::Value;
+/// assert_eq!(Value::from("say \"hi\"").to_string(), r#""say \"hi\"""#);
+/// ```
+impl From<&str> for Value {
+	fn from(value: &str) -> Self {
+		Value::Literal(Literal::new_literal(escape(value)))
+	}
+}
+
+impl From<String> for Value {
+	fn from(value: String) -> Self {
+		Value::from(value.as_str())
+	}
+}
+
+impl From<&String> for Value {
+	fn from(value: &String) -> Self {
+		Value::from(value.as_str())
+	}
+}
+
+impl From<bool> for Value {
+	fn from(value: bool) -> Self {
+		Value::Bool(Bool::new_bool(value))
+	}
+}
+
+impl From<()> for Value {
+	fn from(_: ()) -> Self {
+		Value::null()
+	}
+}
+
+macro_rules! from_integer {
+	($($ty:ty)*) => {
+		$(
+			impl From<$ty> for Value {
+				fn from(value: $ty) -> Self {
+					Value::Number(Number::new_number(value.to_string()))
+				}
+			}
+		)*
+	};
+}
+
+from_integer!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+
+macro_rules! from_float {
+	($($ty:ty)*) => {
+		$(
+			/// NaN and infinity aren't valid JSON so they become `null`
+			impl From<$ty> for Value {
+				fn from(value: $ty) -> Self {
+					if value.is_finite() {
+						Value::Number(Number::new_number(value.to_string()))
+					}
+					else {
+						Value::null()
+					}
+				}
+			}
+		)*
+	};
+}
+
+from_float!(f32 f64);
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+	fn from(value: Option<T>) -> Self {
+		match value {
+			Some(value) => value.into(),
+			None => Value::null(),
+		}
+	}
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+	fn from(value: Vec<T>) -> Self {
+		let value = value.into_iter().map(Into::into).collect();
+		Value::Array(Array::new_array(value))
+	}
+}
+
+impl<T: Clone + Into<Value>> From<&[T]> for Value {
+	fn from(value: &[T]) -> Self {
+		Value::from(value.to_vec())
+	}
+}
+
+impl<T: Into<Value>> From<HashMap<String, T>> for Value {
+	fn from(value: HashMap<String, T>) -> Self {
+		let value = value
+			.into_iter()
+			.map(|(key, value)| (Literal::new_literal(escape(&key)), value.into()))
+			.collect();
+		Value::Object(Object::new_object(value))
+	}
+}
+
+pub(crate) fn invalid_type(value: &Value, expected: &'static str) -> ConversionError {
+	ConversionError::InvalidType(value.span(), expected, value.kind_name())
+}
+
+impl TryFrom<&Value> for bool {
+	type Error = ConversionError;
+
+	fn try_from(value: &Value) -> Result<Self, Self::Error> {
+		match value {
+			Value::Bool(item) => Ok(item.value),
+			_ => Err(invalid_type(value, "boolean")),
+		}
+	}
+}
+
+/// Escape sequences are decoded
+///
+/// ```
+/// # use json_peek::util;
+/// # use std::convert::TryFrom;
+/// let value = util::from_str(r#""a\nb""#).unwrap();
+/// assert_eq!(String::try_from(&value), Ok("a\nb".to_owned()));
+/// ```
+impl TryFrom<&Value> for String {
+	type Error = ConversionError;
+
+	fn try_from(value: &Value) -> Result<Self, Self::Error> {
+		match value {
+			Value::Literal(item) => Ok(unescape(&item.value).into_owned()),
+			_ => Err(invalid_type(value, "string")),
+		}
+	}
+}
+
+macro_rules! try_from_number {
+	($($ty:ty)*) => {
+		$(
+			impl TryFrom<&Value> for $ty {
+				type Error = ConversionError;
+
+				fn try_from(value: &Value) -> Result<Self, Self::Error> {
+					match value {
+						Value::Number(item) => item
+							.value
+							.parse()
+							.map_err(|_| ConversionError::InvalidNumber(item.span, item.value.clone(), stringify!($ty))),
+						_ => Err(invalid_type(value, "number")),
+					}
+				}
+			}
+		)*
+	};
+}
+
+try_from_number!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize f32 f64);
+
+/// `null` become `None`
+impl<T> TryFrom<&Value> for Option<T>
+where
+	T: for<'a> TryFrom<&'a Value, Error = ConversionError>,
+{
+	type Error = ConversionError;
+
+	fn try_from(value: &Value) -> Result<Self, Self::Error> {
+		match value {
+			Value::Null(_) => Ok(None),
+			_ => T::try_from(value).map(Some),
+		}
+	}
+}
+
+impl<T> TryFrom<&Value> for Vec<T>
+where
+	T: for<'a> TryFrom<&'a Value, Error = ConversionError>,
+{
+	type Error = ConversionError;
+
+	fn try_from(value: &Value) -> Result<Self, Self::Error> {
+		match value {
+			Value::Array(item) => item.value.iter().map(T::try_from).collect(),
+			_ => Err(invalid_type(value, "array")),
+		}
+	}
+}
+
+/// Keys are unescaped, when the same key appear more than once after unescaping only one of them is kept
+impl<T> TryFrom<&Value> for HashMap<String, T>
+where
+	T: for<'a> TryFrom<&'a Value, Error = ConversionError>,
+{
+	type Error = ConversionError;
+
+	fn try_from(value: &Value) -> Result<Self, Self::Error> {
+		match value {
+			Value::Object(item) => item
+				.value
+				.iter()
+				.map(|(key, value)| Ok((unescape(&key.value).into_owned(), T::try_from(value)?)))
+				.collect(),
+			_ => Err(invalid_type(value, "object")),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::util;
+	use crate::value::Keyable;
+
+	#[test]
+	fn round_trip() {
+		let mut map = HashMap::new();
+		map.insert("a\"b".to_owned(), vec![Some(1.5), None]);
+		map.insert(String::new(), vec![]);
+
+		let value = Value::from(map.clone());
+		assert_eq!(HashMap::<String, Vec<Option<f64>>>::try_from(&value), Ok(map));
+		assert_eq!(Value::from("").to_string(), r#""""#);
+		assert_eq!(i64::try_from(&Value::from(-42)), Ok(-42));
+		assert_eq!(bool::try_from(&Value::from(true)), Ok(true));
+		assert_eq!(Value::from(f64::NAN), Value::null());
+		assert_eq!(Value::from(&[1u8, 2][..]).to_string(), "[1,2]");
+	}
+
+	#[test]
+	fn conversion_errors() {
+		let value = util::from_str(r#"{ "a": [1, 2.5], "b": 300 }"#).unwrap();
+
+		assert_eq!(
+			Vec::<i64>::try_from(&value.get("a").unwrap()),
+			Err(ConversionError::InvalidNumber(Span::new(11, 14), "2.5".to_owned(), "i64"))
+		);
+		assert_eq!(u8::try_from(&value.get("b").unwrap()), Err(ConversionError::InvalidNumber(Span::new(22, 25), "300".to_owned(), "u8")));
+		assert_eq!(String::try_from(&value), Err(ConversionError::InvalidType(Span::new(0, 27), "string", "object")));
+	}
+}
@@ -0,0 +1,366 @@
+use super::convert::invalid_type;
+use super::{ConversionError, Item, Object, Value};
+use crate::util::{unescape, Span};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// Extract a Rust type out of a [Value](enum.Value.html) while collecting every error instead of stopping at the first one
+///
+/// This is usually implemented with `#[derive(FromValue)]`, fields are read from object members and can be customized with `#[json(...)]`:
+///
+/// - `rename = "name"` read the field from a different key
+/// - `default` or `default = "path"` use `Default::default()` or the given function when the key is missing
+/// - `optional` treat a missing key as `None`, this is the default for fields whose type is `Option<T>`
+/// - `flatten` read the field from the same object as the struct
+/// - `deny_unknown_fields` on the struct report every key that isn't a field
+///
+/// Enums either have only unit variants and are read from a string, or are read from an object by `#[json(tag = "key")]`,
+/// or try every variant in order with `#[json(untagged)]`. Variants can be renamed as well.
+///
+/// ```
+/// # use json_peek::{util, FromValue};
+/// # use json_peek::util::Span;
+/// # use json_peek::value::{ConversionError, Item};
+/// #[derive(Debug, PartialEq, FromValue)]
+/// #[json(deny_unknown_fields)]
+/// struct Display {
+///     title: String,
+///     #[json(rename = "show_toast", default)]
+///     toast: bool,
+///     frame: Option<Item<Frame>>,
+/// }
+///
+/// #[derive(Debug, PartialEq, FromValue)]
+/// enum Frame {
+///     #[json(rename = "task")]
+///     Task,
+///     #[json(rename = "goal")]
+///     Goal,
+/// }
+///
+/// let value = util::from_str(r#"{ "title": "Hello", "frame": "goal" }"#).unwrap();
+/// let display = Display::from_value(&value).unwrap();
+/// assert_eq!(display.frame.unwrap().span, Span::new(29, 35));
+///
+/// let value = util::from_str(r#"{ "toast": true, "frame": "big" }"#).unwrap();
+/// assert_eq!(
+///     Display::from_value(&value),
+///     Err(vec![
+///         ConversionError::MissingField(Span::new(0, 33), "title"),
+///         ConversionError::UnknownVariant(Span::new(26, 31), "big".to_owned()),
+///         ConversionError::UnknownField(Span::new(2, 9), "toast".to_owned()),
+///     ])
+/// );
+/// ```
+pub trait FromValue: Sized {
+	fn from_value(value: &Value) -> Result<Self, Vec<ConversionError>>;
+
+	/// Keys that this type read from an object, used by `flatten` together with `deny_unknown_fields`
+	fn field_names() -> Vec<&'static str> {
+		Vec::new()
+	}
+}
+
+impl Value {
+	/// Shorthand for [FromValue::from_value()](trait.FromValue.html#tymethod.from_value)
+	pub fn extract<T: FromValue>(&self) -> Result<T, Vec<ConversionError>> {
+		T::from_value(self)
+	}
+}
+
+macro_rules! from_value_with_try_from {
+	($($ty:ty)*) => {
+		$(
+			impl FromValue for $ty {
+				fn from_value(value: &Value) -> Result<Self, Vec<ConversionError>> {
+					<$ty>::try_from(value).map_err(|error| vec![error])
+				}
+			}
+		)*
+	};
+}
+
+from_value_with_try_from!(bool String i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize f32 f64);
+
+impl FromValue for Value {
+	fn from_value(value: &Value) -> Result<Self, Vec<ConversionError>> {
+		Ok(value.clone())
+	}
+}
+
+/// Keep the span of the value alongside of it
+impl<T: FromValue> FromValue for Item<T> {
+	fn from_value(value: &Value) -> Result<Self, Vec<ConversionError>> {
+		let mut item = Item::new(value.span(), T::from_value(value)?);
+		item.origin = value.origin();
+		Ok(item)
+	}
+}
+
+impl<T: FromValue> FromValue for Box<T> {
+	fn from_value(value: &Value) -> Result<Self, Vec<ConversionError>> {
+		T::from_value(value).map(Box::new)
+	}
+}
+
+/// `null` become `None`
+impl<T: FromValue> FromValue for Option<T> {
+	fn from_value(value: &Value) -> Result<Self, Vec<ConversionError>> {
+		match value {
+			Value::Null(_) => Ok(None),
+			_ => T::from_value(value).map(Some),
+		}
+	}
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+	fn from_value(value: &Value) -> Result<Self, Vec<ConversionError>> {
+		let array = match value {
+			Value::Array(array) => array,
+			_ => return Err(vec![invalid_type(value, "array")]),
+		};
+
+		let mut errors = Vec::new();
+		let result = array.iter().filter_map(|x| __private::collect(x, T::from_value(x), &mut errors)).collect();
+		__private::finish(errors).map(|_| result)
+	}
+}
+
+impl<T: FromValue> FromValue for HashMap<String, T> {
+	fn from_value(value: &Value) -> Result<Self, Vec<ConversionError>> {
+		let object = match value {
+			Value::Object(object) => object,
+			_ => return Err(vec![invalid_type(value, "object")]),
+		};
+
+		let mut errors = Vec::new();
+		let result = object
+			.iter()
+			.filter_map(|(key, value)| {
+				let value = __private::collect(value, T::from_value(value), &mut errors)?;
+				Some((unescape(&key.value).into_owned(), value))
+			})
+			.collect();
+		__private::finish(errors).map(|_| result)
+	}
+}
+
+/// Helpers used by the code that `#[derive(FromValue)]` generate
+#[doc(hidden)]
+pub mod __private {
+	use super::*;
+
+	pub fn object(value: &Value) -> Result<&Object, Vec<ConversionError>> {
+		match value {
+			Value::Object(object) => Ok(object),
+			_ => Err(vec![invalid_type(value, "object")]),
+		}
+	}
+
+	/// Move the errors of `result` into `errors`, an impl that fail without any error get one for `value` so the failure isn't lost
+	pub fn collect<T>(value: &Value, result: Result<T, Vec<ConversionError>>, errors: &mut Vec<ConversionError>) -> Option<T> {
+		match result {
+			Ok(value) => Some(value),
+			Err(error) if error.is_empty() => {
+				errors.push(invalid_type(value, std::any::type_name::<T>()));
+				None
+			}
+			Err(error) => {
+				errors.extend(error);
+				None
+			}
+		}
+	}
+
+	pub fn finish(errors: Vec<ConversionError>) -> Result<(), Vec<ConversionError>> {
+		if errors.is_empty() {
+			Ok(())
+		}
+		else {
+			Err(errors)
+		}
+	}
+
+	/// Read a field from `object`, `missing` is used when the key doesn't exist
+	pub fn field<T: FromValue>(
+		object: &Object,
+		name: &'static str,
+		missing: Option<fn() -> T>,
+		errors: &mut Vec<ConversionError>,
+	) -> Option<T> {
+		match (object.member(name), missing) {
+			(Some((_, value)), _) => collect(value, T::from_value(value), errors),
+			(None, Some(missing)) => Some(missing()),
+			(None, None) => {
+				errors.push(ConversionError::MissingField(object.span, name));
+				None
+			}
+		}
+	}
+
+	/// Read the string that select which variant of an enum to use
+	pub fn variant(value: &Value) -> Result<(Span, String), Vec<ConversionError>> {
+		match value {
+			Value::Literal(literal) => Ok((literal.span, unescape(&literal.value).into_owned())),
+			_ => Err(vec![invalid_type(value, "string")]),
+		}
+	}
+
+	/// Same as [variant()](fn.variant.html) but the string is read from the `key` member of `object`
+	pub fn tag(object: &Object, key: &'static str) -> Result<(Span, String), Vec<ConversionError>> {
+		match object.member(key) {
+			Some((_, value)) => variant(value),
+			None => Err(vec![ConversionError::MissingField(object.span, key)]),
+		}
+	}
+
+	pub fn deny_unknown_fields(object: &Object, known: &[&'static str], errors: &mut Vec<ConversionError>) {
+		for (key, _) in object.iter() {
+			let name = unescape(&key.value);
+			if !known.contains(&name.as_ref()) {
+				errors.push(ConversionError::UnknownField(key.span, name.into_owned()));
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::util;
+	use crate::FromValue;
+
+	#[derive(Debug, PartialEq, FromValue)]
+	#[json(crate = "crate", deny_unknown_fields)]
+	struct Advancement {
+		parent: Option<String>,
+		#[json(flatten)]
+		meta: Meta,
+		rewards: Vec<Reward>,
+	}
+
+	#[derive(Debug, PartialEq, FromValue)]
+	#[json(crate = "crate")]
+	struct Meta {
+		#[json(default = "default_version")]
+		version: u32,
+	}
+
+	fn default_version() -> u32 {
+		4
+	}
+
+	#[derive(Debug, PartialEq, FromValue)]
+	#[json(crate = "crate", tag = "type")]
+	enum Reward {
+		#[json(rename = "experience")]
+		Experience { amount: i64 },
+		#[json(rename = "recipes")]
+		Recipes(Recipes),
+		#[json(rename = "none")]
+		Nothing,
+	}
+
+	#[derive(Debug, PartialEq, FromValue)]
+	#[json(crate = "crate")]
+	struct Recipes {
+		#[json(rename = "list")]
+		recipes: Vec<Item<String>>,
+	}
+
+	#[derive(Debug, PartialEq, FromValue)]
+	#[json(crate = "crate", untagged)]
+	enum Count {
+		Exact(u32),
+		Range { min: u32, max: u32 },
+		Unset,
+	}
+
+	#[test]
+	fn derive_struct_and_tagged_enum() {
+		let value = util::from_str(
+			r#"{
+				"rewards": [{ "type": "experience", "amount": 10 }, { "type": "recipes", "list": ["a"] }, { "type": "none" }]
+			}"#,
+		)
+		.unwrap();
+
+		assert_eq!(
+			value.extract(),
+			Ok(Advancement {
+				parent: None,
+				meta: Meta { version: 4 },
+				rewards: vec![
+					Reward::Experience { amount: 10 },
+					Reward::Recipes(Recipes { recipes: vec![Item::synthetic("a".to_owned())] }),
+					Reward::Nothing,
+				],
+			})
+		);
+	}
+
+	#[test]
+	fn collect_every_error() {
+		let content = r#"{ "version": 5, "extra": 1, "rewards": [{ "type": "experience" }, { "type": "gold" }, 1] }"#;
+		let value = util::from_str(content).unwrap();
+
+		assert_eq!(
+			Advancement::from_value(&value),
+			Err(vec![
+				ConversionError::MissingField(Span::new(40, 64), "amount"),
+				ConversionError::UnknownVariant(Span::new(76, 82), "gold".to_owned()),
+				ConversionError::InvalidType(Span::new(86, 87), "object", "number"),
+				ConversionError::UnknownField(Span::new(16, 23), "extra".to_owned()),
+			])
+		);
+	}
+
+	/// Fail without telling why
+	#[derive(Debug, PartialEq)]
+	struct Silent;
+
+	impl FromValue for Silent {
+		fn from_value(_: &Value) -> Result<Self, Vec<ConversionError>> {
+			Err(Vec::new())
+		}
+	}
+
+	#[derive(Debug, PartialEq, FromValue)]
+	#[json(crate = "crate")]
+	struct Wrapper {
+		silent: Silent,
+	}
+
+	#[test]
+	fn error_without_reason() {
+		let value = util::from_str(r#"{ "silent": 1 }"#).unwrap();
+		let silent = std::any::type_name::<Silent>();
+
+		assert_eq!(Wrapper::from_value(&value), Err(vec![ConversionError::InvalidType(Span::new(12, 13), silent, "number")]));
+		assert_eq!(
+			Vec::<Silent>::from_value(&util::from_str("[1]").unwrap()),
+			Err(vec![ConversionError::InvalidType(Span::new(1, 2), silent, "number")])
+		);
+	}
+
+	#[test]
+	fn untagged_enum() {
+		let extract = |content: &str| Count::from_value(&util::from_str(content).unwrap());
+
+		assert_eq!(extract("3"), Ok(Count::Exact(3)));
+		assert_eq!(extract(r#"{ "min": 1, "max": 2 }"#), Ok(Count::Range { min: 1, max: 2 }));
+		assert_eq!(extract("null"), Ok(Count::Unset));
+		assert_eq!(
+			extract("true"),
+			Err(vec![ConversionError::NoMatchingVariant(
+				Span::new(0, 4),
+				"Count",
+				vec![
+					ConversionError::InvalidType(Span::new(0, 4), "number", "boolean"),
+					ConversionError::InvalidType(Span::new(0, 4), "object", "boolean"),
+					ConversionError::InvalidType(Span::new(0, 4), "null", "boolean"),
+				]
+			)])
+		);
+	}
+}
@@ -11,62 +11,99 @@ pub type Literal = Item<String>;
 pub type Bool = Item<bool>;
 pub type Null = Item<()>;
 
+/// Where a node come from, this decide whether its span can be trusted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Origin {
+	/// Parsed from the source, span point at the exact text of the node
+	Source,
+	/// Created in code, it doesn't have a span so [source_span()](struct.Item.html#method.source_span) is `None`
+	/// and the `span` field is left empty at `0`
+	Synthetic,
+	/// Built out of other nodes (merged, patched, mutated), span point at the source node it's derived from
+	/// but the text there might not match anymore
+	Derived,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Item<T> {
 	pub span: Span,
 	pub value: T,
+	pub origin: Origin,
 }
 
 impl<T> Item<T> {
+	/// Create an item that come from `span` of the source
 	pub const fn new(span: Span, value: T) -> Item<T> {
-		Item { span, value }
+		Item { span, value, origin: Origin::Source }
+	}
+
+	/// Create an item that doesn't come from the source
+	pub const fn synthetic(value: T) -> Item<T> {
+		Item { span: Span::new(0, 0), value, origin: Origin::Synthetic }
+	}
+
+	/// Create an item that is derived from the node at `span`
+	pub const fn derived(span: Span, value: T) -> Item<T> {
+		Item { span, value, origin: Origin::Derived }
 	}
 
 	pub fn span(&self) -> Span {
 		self.span
 	}
+
+	pub fn origin(&self) -> Origin {
+		self.origin
+	}
+
+	/// Span of this item only if it come straight from the source
+	pub fn source_span(&self) -> Option<Span> {
+		match self.origin {
+			Origin::Source => Some(self.span),
+			_ => None,
+		}
+	}
+
+	/// Mark that this item no longer match its source text, synthetic item stay synthetic
+	pub fn mark_derived(&mut self) {
+		if self.origin == Origin::Source {
+			self.origin = Origin::Derived;
+		}
+	}
 }
 
 impl Literal {
 	pub fn new_literal(value: impl Into<String>) -> Self {
-		let value = value.into();
-		let span = Span::default();
-		Item { span, value }
+		Item::synthetic(value.into())
 	}
 }
 
 impl Number {
 	pub fn new_number(value: String) -> Self {
-		let span = Span::default();
-		Item { span, value }
+		Item::synthetic(value)
 	}
 }
 
 impl Array {
 	pub fn new_array(value: Vec<Value>) -> Self {
-		let span = Span::default();
-		Item { span, value }
+		Item::synthetic(value)
 	}
 }
 
 impl Bool {
 	pub fn new_bool(value: bool) -> Self {
-		let span = Span::default();
-		Item { span, value }
+		Item::synthetic(value)
 	}
 }
 
 impl Null {
 	pub fn new_null() -> Self {
-		let span = Span::default();
-		Item { span, value: () }
+		Item::synthetic(())
 	}
 }
 
 impl Object {
 	pub fn new_object(value: HashMap<Literal, Value>) -> Self {
-		let span = Span::default();
-		Item { span, value }
+		Item::synthetic(value)
 	}
 }
 
@@ -92,6 +129,6 @@ impl From<&str> for Literal {
 
 impl From<Token<'_>> for Literal {
 	fn from(token: Token) -> Self {
-		Item::new(token.span, token.value().to_owned())
+		Item::new(token.full_span(), token.value().to_owned())
 	}
 }
\ No newline at end of file
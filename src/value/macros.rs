@@ -0,0 +1,116 @@
+/// Build a [Value](value/enum.Value.html) out of JSON-like syntax, every node is [synthetic](value/enum.Origin.html#variant.Synthetic)
+///
+/// Object keys are either string literals or any expression that implements `ToString` wrapped in parentheses.
+/// Values are either nested JSON or any expression that implements `Into<Value>`, strings are escaped so they can be written back out.
+///
+/// ```
+/// # use json_peek::{json, util};
+/// let name = "tick";
+/// let value = json!({
+///     "criteria": {
+///         (name): { "trigger": format!("minecraft:{}", name) }
+///     },
+///     "rewards": [1, -2.5, null, true, "say \"hi\""],
+///     "parent": None::<&str>
+/// });
+///
+/// let expected = util::from_str(r#"{
+///     "criteria": { "tick": { "trigger": "minecraft:tick" } },
+///     "rewards": [1, -2.5, null, true, "say \"hi\""],
+///     "parent": null
+/// }"#).unwrap();
+/// assert_eq!(value, expected);
+/// assert_eq!(value.source_span(), None);
+/// ```
+#[macro_export]
+macro_rules! json {
+	// Array elements, single token elements are matched first to keep recursion shallow
+	(@array [$($elements:expr,)*]) => {
+		vec![$($elements,)*]
+	};
+	(@array [$($elements:expr,)*] $value:tt , $($rest:tt)*) => {
+		$crate::json!(@array [$($elements,)* $crate::json!($value),] $($rest)*)
+	};
+	(@array [$($elements:expr,)*] $value:tt) => {
+		$crate::json!(@array [$($elements,)* $crate::json!($value),])
+	};
+	(@array [$($elements:expr,)*] $($rest:tt)+) => {
+		$crate::json!(@element [$($elements,)*] () $($rest)+)
+	};
+	(@element [$($elements:expr,)*] ($($value:tt)+) , $($rest:tt)*) => {
+		$crate::json!(@array [$($elements,)* $crate::json!($($value)+),] $($rest)*)
+	};
+	(@element [$($elements:expr,)*] ($($value:tt)+)) => {
+		$crate::json!(@array [$($elements,)* $crate::json!($($value)+),])
+	};
+	(@element [$($elements:expr,)*] ($($value:tt)*) $next:tt $($rest:tt)*) => {
+		$crate::json!(@element [$($elements,)*] ($($value)* $next) $($rest)*)
+	};
+
+	// Object members, inserted one by one into `$object`
+	(@object $object:ident) => {};
+	(@object $object:ident $key:tt : $value:tt , $($rest:tt)*) => {
+		$crate::json!(@insert $object $key ($value));
+		$crate::json!(@object $object $($rest)*);
+	};
+	(@object $object:ident $key:tt : $value:tt) => {
+		$crate::json!(@insert $object $key ($value));
+	};
+	(@object $object:ident $key:tt : $($rest:tt)+) => {
+		$crate::json!(@member $object $key () $($rest)+);
+	};
+	(@member $object:ident $key:tt ($($value:tt)+) , $($rest:tt)*) => {
+		$crate::json!(@insert $object $key ($($value)+));
+		$crate::json!(@object $object $($rest)*);
+	};
+	(@member $object:ident $key:tt ($($value:tt)+)) => {
+		$crate::json!(@insert $object $key ($($value)+));
+	};
+	(@member $object:ident $key:tt ($($value:tt)*) $next:tt $($rest:tt)*) => {
+		$crate::json!(@member $object $key ($($value)* $next) $($rest)*);
+	};
+	(@insert $object:ident $key:tt ($($value:tt)+)) => {
+		let key = $crate::util::escape(&::std::string::ToString::to_string(&$key)).into_owned();
+		$object.insert($crate::value::Literal::new_literal(key), $crate::json!($($value)+));
+	};
+
+	(null) => {
+		$crate::value::Value::null()
+	};
+	([ $($elements:tt)* ]) => {
+		$crate::value::Value::Array($crate::value::Array::new_array($crate::json!(@array [] $($elements)*)))
+	};
+	({ $($members:tt)* }) => {{
+		#[allow(unused_mut)]
+		let mut object = ::std::collections::HashMap::new();
+		$crate::json!(@object object $($members)*);
+		$crate::value::Value::Object($crate::value::Object::new_object(object))
+	}};
+	($other:expr) => {
+		$crate::value::Value::from($other)
+	};
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::util;
+	use crate::value::{Origin, Value};
+
+	#[test]
+	fn build_values() {
+		let items = vec![1, 2];
+		let value = json!([
+			{},
+			[],
+			items.clone(),
+			items.len() as u8 + 1,
+			-0.5,
+			{ "a": { "b": [null] }, "c\nd": false, },
+		]);
+
+		assert_eq!(value, util::from_str(r#"[{}, [], [1, 2], 3, -0.5, { "a": { "b": [null] }, "c\nd": false }]"#).unwrap());
+		assert_eq!(value.origin(), Origin::Synthetic);
+		assert_eq!(json!(f64::NAN), Value::null());
+		assert_eq!(json!("\\").to_string(), r#""\\""#);
+	}
+}
@@ -3,9 +3,28 @@ use crate::{
 	util::Span,
 };
 use std::collections::HashMap;
+use std::fmt;
 
+mod compare;
+mod convert;
+mod from_value;
 mod item;
+mod macros;
+mod mutate;
+mod validate;
+mod visit;
+pub use compare::{Comparison, Mismatch, MismatchKind};
+pub use convert::ConversionError;
+pub use from_value::FromValue;
+#[doc(hidden)]
+pub use from_value::__private;
 pub use item::*;
+pub use mutate::Entry;
+pub use validate::SpanError;
+pub use visit::{
+	fold_array, fold_object, fold_value, walk_array, walk_array_mut, walk_object, walk_object_mut, walk_value, walk_value_mut,
+	BreadthFirst, DepthFirst, Fold, Visitor, VisitorMut,
+};
 
 #[derive(Debug, Clone)]
 pub enum Value {
@@ -18,8 +37,9 @@ pub enum Value {
 }
 
 impl Value {
+	/// Synthetic `null`, see [Origin](enum.Origin.html)
 	pub fn null() -> Value {
-		Self::new_null(Span::new(0, 0))
+		Value::Null(Null::new_null())
 	}
 
 	pub fn is_object(&self) -> bool {
@@ -64,6 +84,20 @@ impl Value {
 		}
 	}
 
+	/// Name of this value's type to use inside of an error message
+	pub fn kind_name(&self) -> &'static str {
+		match self {
+			Value::Object(_) => "object",
+			Value::Array(_) => "array",
+			Value::Literal(_) => "string",
+			Value::Number(_) => "number",
+			Value::Bool(_) => "boolean",
+			Value::Null(_) => "null",
+		}
+	}
+
+	/// Span of this value, it doesn't point at anything if this value is synthetic so
+	/// use [source_span()](#method.source_span) when that matter
 	pub fn span(&self) -> Span {
 		match self {
 			Self::Object(item) => item.span(),
@@ -75,6 +109,52 @@ impl Value {
 		}
 	}
 
+	pub fn origin(&self) -> Origin {
+		match self {
+			Self::Object(item) => item.origin(),
+			Self::Array(item) => item.origin(),
+			Self::Literal(item) => item.origin(),
+			Self::Number(item) => item.origin(),
+			Self::Null(item) => item.origin(),
+			Self::Bool(item) => item.origin(),
+		}
+	}
+
+	/// Span of this value only if it come straight from the source, see [Origin](enum.Origin.html)
+	///
+	/// ```
+	/// # use json_peek::util::{self, Span};
+	/// # use json_peek::value::Value;
+	/// let mut value = util::from_str("[1]").unwrap();
+	/// assert_eq!(value.source_span(), Some(Span::new(0, 3)));
+	///
+	/// value.as_array_mut().unwrap().push(Value::null());
+	/// assert_eq!(value.source_span(), None);
+	/// assert_eq!(value.span(), Span::new(0, 3));
+	/// ```
+	pub fn source_span(&self) -> Option<Span> {
+		match self {
+			Self::Object(item) => item.source_span(),
+			Self::Array(item) => item.source_span(),
+			Self::Literal(item) => item.source_span(),
+			Self::Number(item) => item.source_span(),
+			Self::Null(item) => item.source_span(),
+			Self::Bool(item) => item.source_span(),
+		}
+	}
+
+	/// Mark that this value no longer match its source text, see [Item::mark_derived()](struct.Item.html#method.mark_derived)
+	pub fn mark_derived(&mut self) {
+		match self {
+			Self::Object(item) => item.mark_derived(),
+			Self::Array(item) => item.mark_derived(),
+			Self::Literal(item) => item.mark_derived(),
+			Self::Number(item) => item.mark_derived(),
+			Self::Null(item) => item.mark_derived(),
+			Self::Bool(item) => item.mark_derived(),
+		}
+	}
+
 	/// Transform `Value` into `Some(Value)` of itself.
 	/// This is helpful for testing since most of the operation return an `Option<Value>`
 	pub const fn some(self) -> Option<Self> {
@@ -113,34 +193,35 @@ impl Value {
 		Value::Bool(item)
 	}
 
+	/// Build a [synthetic](enum.Origin.html#variant.Synthetic) object, mostly useful for comparing in tests
 	pub fn test_object(value: HashMap<Literal, Value>) -> Value {
-		Value::new_object(Span::test(), value)
+		Value::Object(Item::synthetic(value))
 	}
 
 	pub fn test_array(value: Vec<Value>) -> Value {
-		Value::new_array(Span::test(), value)
+		Value::Array(Item::synthetic(value))
 	}
 
 	pub fn test_string(value: &str) -> Value {
-		Value::new_string(Span::test(), value)
+		Value::Literal(Item::synthetic(value.to_owned()))
 	}
 
 	pub fn test_number(value: &str) -> Value {
-		Value::new_number(Span::test(), value)
+		Value::Number(Item::synthetic(value.to_owned()))
 	}
 
 	pub fn test_null() -> Value {
-		Value::new_null(Span::test())
+		Value::Null(Item::synthetic(()))
 	}
 
 	pub fn test_bool(value: bool) -> Value {
-		Value::new_bool(Span::test(), value)
+		Value::Bool(Item::synthetic(value))
 	}
 }
 
 impl<'a> From<Token<'a>> for Option<Value> {
 	fn from(token: Token) -> Option<Value> {
-		let span = token.span;
+		let span = token.full_span();
 		match token.kind {
 			TokenKind::Number => Some(Value::new_number(span, token)),
 			TokenKind::String => Some(Value::new_string(span, token)),
@@ -155,10 +236,44 @@ impl<'a> From<Token<'a>> for Option<Value> {
 	}
 }
 
-impl From<&str> for Value {
-	fn from(key: &str) -> Self {
-		let span = Span::new(0, key.len() - 1);
-		Value::new_string(span, key)
+/// Serialize `Value` back into compact JSON
+///
+/// Strings are written as-is since they are never unescaped in the first place.
+///
+/// ```
+/// # use json_peek::util;
+/// let value = util::from_str(r#"[1, "two", null]"#).unwrap();
+///
+/// assert_eq!(value.to_string(), r#"[1,"two",null]"#);
+/// ```
+impl fmt::Display for Value {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Object(item) => {
+				write!(f, "{{")?;
+				for (index, (key, value)) in item.value.iter().enumerate() {
+					if index > 0 {
+						write!(f, ",")?;
+					}
+					write!(f, "\"{}\":{}", key.value, value)?;
+				}
+				write!(f, "}}")
+			}
+			Self::Array(item) => {
+				write!(f, "[")?;
+				for (index, value) in item.value.iter().enumerate() {
+					if index > 0 {
+						write!(f, ",")?;
+					}
+					write!(f, "{}", value)?;
+				}
+				write!(f, "]")
+			}
+			Self::Literal(item) => write!(f, "\"{}\"", item.value),
+			Self::Number(item) => write!(f, "{}", item.value),
+			Self::Bool(item) => write!(f, "{}", item.value),
+			Self::Null(_) => write!(f, "null"),
+		}
 	}
 }
 
@@ -292,6 +407,8 @@ mod tests {
 		]);
 
 		assert_eq!(sample_data.index(0), Value::test_number("3").some());
+		assert_eq!(sample_data.origin(), Origin::Synthetic);
+		assert_eq!(sample_data.source_span(), None);
 	}
 
 
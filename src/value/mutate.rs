@@ -0,0 +1,364 @@
+use super::{Array, Literal, Object, Origin, Value};
+use crate::util::unescape;
+use std::collections::hash_map;
+use std::mem;
+
+impl Value {
+	pub fn as_object(&self) -> Option<&Object> {
+		match self {
+			Value::Object(item) => Some(item),
+			_ => None,
+		}
+	}
+
+	pub fn as_object_mut(&mut self) -> Option<&mut Object> {
+		match self {
+			Value::Object(item) => Some(item),
+			_ => None,
+		}
+	}
+
+	pub fn as_array(&self) -> Option<&Array> {
+		match self {
+			Value::Array(item) => Some(item),
+			_ => None,
+		}
+	}
+
+	pub fn as_array_mut(&mut self) -> Option<&mut Array> {
+		match self {
+			Value::Array(item) => Some(item),
+			_ => None,
+		}
+	}
+
+	/// Take the value out and leave a synthetic `null` in its place
+	///
+	/// ```
+	/// # use json_peek::util;
+	/// let mut value = util::from_str(r#"{ "a": [1] }"#).unwrap();
+	/// let array = value.as_object_mut().unwrap().get_mut("a").unwrap().take();
+	///
+	/// assert_eq!(array.to_string(), "[1]");
+	/// assert_eq!(value.to_string(), r#"{"a":null}"#);
+	/// ```
+	pub fn take(&mut self) -> Value {
+		mem::replace(self, Value::null())
+	}
+}
+
+/// Mutation on object, keys are compared exactly as they appear in the source and new keys are [synthetic](enum.Origin.html#variant.Synthetic).
+/// Any change to the members mark the object as [derived](enum.Origin.html#variant.Derived),
+/// so does handing out a mutable member through [get_mut()](#method.get_mut) or [iter_mut()](#method.iter_mut) since it can be replaced.
+///
+/// ```
+/// # use json_peek::util;
+/// # use json_peek::value::Value;
+/// let mut value = util::from_str(r#"{ "a": 1, "b": 2 }"#).unwrap();
+/// let object = value.as_object_mut().unwrap();
+///
+/// object.insert("c", Value::from("three"));
+/// object.remove("a");
+/// object.entry("b").or_insert_with(Value::null);
+///
+/// let keys: Vec<_> = object.iter().map(|(key, _)| key.value.as_str()).collect();
+/// assert_eq!(keys, ["b", "c"]);
+/// ```
+impl Object {
+	pub fn len(&self) -> usize {
+		self.value.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.value.is_empty()
+	}
+
+	pub fn get_mut(&mut self, key: impl Into<Literal>) -> Option<&mut Value> {
+		let value = self.value.get_mut(&key.into())?;
+		mark_derived(&mut self.origin);
+		Some(value)
+	}
+
+	/// Find the member whose key is equal to `name` after unescaping
+	pub fn member(&self, name: &str) -> Option<(&Literal, &Value)> {
+		self.value.iter().find(|(key, _)| unescape(&key.value) == name)
+	}
+
+	/// Insert `value` under `key` and return the previous value, existing key keep its original span
+	pub fn insert(&mut self, key: impl Into<Literal>, value: Value) -> Option<Value> {
+		self.mark_derived();
+		self.value.insert(key.into(), value)
+	}
+
+	pub fn remove(&mut self, key: impl Into<Literal>) -> Option<Value> {
+		let removed = self.value.remove(&key.into());
+		if removed.is_some() {
+			self.mark_derived();
+		}
+		removed
+	}
+
+	/// Get the member under `key` for in-place manipulation, looking it up doesn't change the object's origin
+	pub fn entry(&mut self, key: impl Into<Literal>) -> Entry<'_> {
+		Entry {
+			entry: self.value.entry(key.into()),
+			origin: &mut self.origin,
+		}
+	}
+
+	pub fn retain(&mut self, f: impl FnMut(&Literal, &mut Value) -> bool) {
+		let len = self.value.len();
+		self.value.retain(f);
+		if self.value.len() != len {
+			self.mark_derived();
+		}
+	}
+
+	/// Iterate through `(key, value)` pairs in the order they appear in the source, synthetic keys come last
+	pub fn iter(&self) -> impl Iterator<Item = (&Literal, &Value)> {
+		let mut members: Vec<_> = self.value.iter().collect();
+		members.sort_by(|(a, _), (b, _)| source_order(a).cmp(&source_order(b)));
+		members.into_iter()
+	}
+
+	/// Same as [iter()](#method.iter) but with mutable values
+	pub fn iter_mut(&mut self) -> impl Iterator<Item = (&Literal, &mut Value)> {
+		self.mark_derived();
+		self.members_mut()
+	}
+
+	/// Same as [iter_mut()](#method.iter_mut) without changing the object's origin, the caller has to mark it
+	pub(super) fn members_mut(&mut self) -> impl Iterator<Item = (&Literal, &mut Value)> {
+		let mut members: Vec<_> = self.value.iter_mut().collect();
+		members.sort_by(|(a, _), (b, _)| source_order(a).cmp(&source_order(b)));
+		members.into_iter()
+	}
+}
+
+/// A member of an [Object](type.Object.html) that might not exist yet, see [Object::entry()](type.Object.html#method.entry)
+///
+/// The object is only marked as [derived](enum.Origin.html#variant.Derived) once a member is inserted or actually changed through this.
+///
+/// ```
+/// # use json_peek::util;
+/// # use json_peek::value::{Origin, Value};
+/// let mut value = util::from_str(r#"{ "a": 1 }"#).unwrap();
+/// let object = value.as_object_mut().unwrap();
+///
+/// object.entry("a").or_insert_with(Value::null);
+/// assert_eq!(object.origin, Origin::Source);
+///
+/// object.entry("b").or_insert_with(Value::null);
+/// assert_eq!(object.origin, Origin::Derived);
+/// ```
+pub struct Entry<'a> {
+	entry: hash_map::Entry<'a, Literal, Value>,
+	origin: &'a mut Origin,
+}
+
+impl<'a> Entry<'a> {
+	pub fn key(&self) -> &Literal {
+		self.entry.key()
+	}
+
+	pub fn or_insert(self, default: Value) -> &'a mut Value {
+		self.or_insert_with(|| default)
+	}
+
+	pub fn or_insert_with(self, default: impl FnOnce() -> Value) -> &'a mut Value {
+		match self.entry {
+			hash_map::Entry::Occupied(entry) => entry.into_mut(),
+			hash_map::Entry::Vacant(entry) => {
+				mark_derived(self.origin);
+				entry.insert(default())
+			}
+		}
+	}
+
+	pub fn and_modify(mut self, f: impl FnOnce(&mut Value)) -> Self {
+		if let hash_map::Entry::Occupied(entry) = &mut self.entry {
+			let before = entry.get().clone();
+			f(entry.get_mut());
+			if *entry.get() != before {
+				mark_derived(self.origin);
+			}
+		}
+		self
+	}
+
+	/// Set the member to `value` and return the previous one, an existing key keep its original span
+	pub fn insert(self, value: Value) -> Option<Value> {
+		mark_derived(self.origin);
+		match self.entry {
+			hash_map::Entry::Occupied(mut entry) => Some(entry.insert(value)),
+			hash_map::Entry::Vacant(entry) => {
+				entry.insert(value);
+				None
+			}
+		}
+	}
+}
+
+/// Same as [Item::mark_derived()](struct.Item.html#method.mark_derived) for an origin that is borrowed on its own
+fn mark_derived(origin: &mut Origin) {
+	if *origin == Origin::Source {
+		*origin = Origin::Derived;
+	}
+}
+
+/// Sort key of an object member, synthetic keys don't have a position so they are sorted by name after everything else
+pub(super) fn source_order(key: &Literal) -> (bool, usize, &str) {
+	match key.origin {
+		Origin::Synthetic => (true, 0, &key.value),
+		_ => (false, key.span.start, &key.value),
+	}
+}
+
+/// Mutation on array, any change to the elements mark the array as [derived](enum.Origin.html#variant.Derived)
+/// and so does handing out a mutable element through [get_mut()](#method.get_mut) or [iter_mut()](#method.iter_mut)
+///
+/// ```
+/// # use json_peek::util;
+/// # use json_peek::value::Value;
+/// let mut value = util::from_str("[1, 2, 3]").unwrap();
+/// let array = value.as_array_mut().unwrap();
+///
+/// array.push(Value::from("four"));
+/// array.retain(|x| *x != "2");
+/// array.remove(0);
+///
+/// assert_eq!(value.to_string(), r#"[3,"four"]"#);
+/// ```
+impl Array {
+	pub fn len(&self) -> usize {
+		self.value.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.value.is_empty()
+	}
+
+	pub fn get_mut(&mut self, index: usize) -> Option<&mut Value> {
+		let value = self.value.get_mut(index)?;
+		mark_derived(&mut self.origin);
+		Some(value)
+	}
+
+	pub fn push(&mut self, value: Value) {
+		self.mark_derived();
+		self.value.push(value)
+	}
+
+	/// Insert `value` at `index`, return the value back if `index` is out of bound
+	pub fn insert(&mut self, index: usize, value: Value) -> Result<(), Value> {
+		if index > self.value.len() {
+			return Err(value);
+		}
+		self.mark_derived();
+		self.value.insert(index, value);
+		Ok(())
+	}
+
+	/// Remove the value at `index`, `None` if `index` is out of bound
+	pub fn remove(&mut self, index: usize) -> Option<Value> {
+		if index < self.value.len() {
+			self.mark_derived();
+			Some(self.value.remove(index))
+		}
+		else {
+			None
+		}
+	}
+
+	pub fn retain(&mut self, f: impl FnMut(&Value) -> bool) {
+		let len = self.value.len();
+		self.value.retain(f);
+		if self.value.len() != len {
+			self.mark_derived();
+		}
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item = &Value> {
+		self.value.iter()
+	}
+
+	pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Value> {
+		self.mark_derived();
+		self.value.iter_mut()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::util::{self, Span};
+	use crate::value::{Keyable, Origin, Value};
+
+	#[test]
+	fn synthetic_nodes() {
+		let content = r#"{ "a": [1] }"#;
+		let mut value = util::from_str(content).unwrap();
+		let object = value.as_object_mut().unwrap();
+
+		object.insert("b", Value::null());
+		object.get_mut("a").unwrap().as_array_mut().unwrap().push(Value::from("x"));
+
+		let (key, new) = object.iter().nth(1).unwrap();
+		assert_eq!(key.source_span(), None);
+		assert_eq!(key.origin, Origin::Synthetic);
+		assert_eq!(new.origin(), Origin::Synthetic);
+		assert_eq!(new.source_span(), None);
+
+		let (key, old) = object.iter().next().unwrap();
+		assert_eq!(key.span, Span::new(2, 5));
+		assert_eq!(old.span(), Span::new(7, 10));
+		assert_eq!(old.origin(), Origin::Derived);
+		assert_eq!(old.as_array().unwrap().value[0].source_span(), Some(Span::new(8, 9)));
+
+		assert_eq!(value.origin(), Origin::Derived);
+		assert_eq!(value.validate_spans(content), Ok(()));
+	}
+
+	#[test]
+	fn entry_and_retain() {
+		let mut value = util::from_str(r#"{ "a": 1, "b": 2, "c": 3 }"#).unwrap();
+		let object = value.as_object_mut().unwrap();
+
+		object.entry("a").or_insert_with(Value::null);
+		object.entry("a").and_modify(|_| ());
+		assert_eq!(object.origin, Origin::Source);
+
+		assert_eq!(object.entry("a").insert(Value::from("one")), Some(Value::from(1)));
+		assert_eq!(object.origin, Origin::Derived);
+		object.retain(|key, _| key.value != "b");
+
+		assert_eq!(object.len(), 2);
+		assert_eq!(value.get("a").unwrap(), "one");
+		assert_eq!(value.as_array(), None);
+
+		let mut value = util::from_str(r#"{ "a": 1 }"#).unwrap();
+		value.as_object_mut().unwrap().entry("a").and_modify(|x| *x = Value::from(10));
+		assert_eq!(value.origin(), Origin::Derived);
+		assert_eq!(value.get("a").unwrap(), "10");
+	}
+
+	#[test]
+	fn mutable_access() {
+		let mut value = util::from_str(r#"{ "a": [1, 2] }"#).unwrap();
+		let object = value.as_object_mut().unwrap();
+		assert_eq!(object.get_mut("b"), None);
+		assert_eq!(object.origin, Origin::Source);
+
+		*object.get_mut("a").unwrap().as_array_mut().unwrap().get_mut(0).unwrap() = Value::from(3);
+		assert_eq!(value.origin(), Origin::Derived);
+		assert_eq!(value.get("a").unwrap().origin(), Origin::Derived);
+
+		let mut value = util::from_str(r#"{ "a": [1, 2] }"#).unwrap();
+		let object = value.as_object_mut().unwrap();
+		for (_, member) in object.iter_mut() {
+			member.as_array_mut().unwrap().iter_mut().for_each(|x| *x = Value::null());
+		}
+		assert_eq!(value.origin(), Origin::Derived);
+		assert_eq!(value.get("a").unwrap().origin(), Origin::Derived);
+		assert_eq!(value.to_string(), r#"{"a":[null,null]}"#);
+	}
+}
@@ -0,0 +1,155 @@
+use super::{Origin, Value};
+use crate::util::Span;
+use thiserror::Error;
+
+/// Problem found by [Value::validate_spans()](enum.Value.html#method.validate_spans)
+#[derive(Debug, Error, PartialEq, Eq, Clone, Copy)]
+pub enum SpanError {
+	/// Span's end is before its start
+	#[error("Span {0} is inverted")]
+	Inverted(Span),
+
+	/// Span is past the end of source or doesn't land on a `char` boundary
+	#[error("Span {0} is out of bound")]
+	OutOfBound(Span),
+
+	/// Span isn't inside of its parent's span
+	#[error("Span {0} is outside of its parent {1}")]
+	NotNested(Span, Span),
+
+	/// The text that span is pointing at isn't what the node contain
+	#[error("Span {0} doesn't match its value")]
+	Mismatch(Span),
+}
+
+impl Value {
+	/// Get the source text that this value was parsed from
+	///
+	/// ```
+	/// # use json_peek::util;
+	/// # use json_peek::value::prelude::*;
+	/// let content = r#"{ "foo": "bar" }"#;
+	/// let value = util::from_str(content).unwrap();
+	///
+	/// assert_eq!(value.get("foo").unwrap().source_text(content), Some(r#""bar""#));
+	/// ```
+	pub fn source_text<'s>(&self, source: &'s str) -> Option<&'s str> {
+		source.get(self.span().range())
+	}
+
+	/// Walk the whole tree and make sure every span follow the contract described in [Span](../util/struct.Span.html),
+	/// this is mostly useful as a debugging tool and inside of tests.
+	///
+	/// ```
+	/// # use json_peek::util;
+	/// let content = r#"{ "foo": [1, true, null] }"#;
+	/// let value = util::from_str(content).unwrap();
+	///
+	/// assert_eq!(value.validate_spans(content), Ok(()));
+	/// ```
+	pub fn validate_spans(&self, source: &str) -> Result<(), SpanError> {
+		let root = Span::new(0, source.len());
+		validate(self, source, root)
+	}
+}
+
+fn validate(value: &Value, source: &str, parent: Span) -> Result<(), SpanError> {
+	let span = value.span();
+	// Synthetic node doesn't come from the source so there is nothing to check
+	if value.origin() == Origin::Synthetic {
+		return Ok(());
+	}
+	let text = check_span(span, source, parent)?;
+
+	let is_match = match value {
+		Value::Object(item) => {
+			for (key, value) in item.value.iter() {
+				validate(value, source, span)?;
+				if key.origin == Origin::Synthetic {
+					continue;
+				}
+				let text = check_span(key.span, source, span)?;
+				if !is_quoted(text, &key.value) {
+					return Err(SpanError::Mismatch(key.span));
+				}
+			}
+			text.starts_with('{') && text.ends_with('}')
+		}
+		Value::Array(item) => {
+			for value in item.value.iter() {
+				validate(value, source, span)?;
+			}
+			text.starts_with('[') && text.ends_with(']')
+		}
+		Value::Literal(item) => is_quoted(text, &item.value),
+		Value::Number(item) => text == item.value,
+		Value::Bool(item) => text == item.value.to_string(),
+		Value::Null(_) => text == "null",
+	};
+
+	// Derived node still has to be in bound but its text might have changed
+	if is_match || value.origin() == Origin::Derived {
+		Ok(())
+	}
+	else {
+		Err(SpanError::Mismatch(span))
+	}
+}
+
+fn check_span(span: Span, source: &str, parent: Span) -> Result<&str, SpanError> {
+	if span.start > span.end {
+		return Err(SpanError::Inverted(span));
+	}
+
+	let text = source.get(span.range()).ok_or(SpanError::OutOfBound(span))?;
+
+	if span.start < parent.start || span.end > parent.end {
+		return Err(SpanError::NotNested(span, parent));
+	}
+
+	Ok(text)
+}
+
+fn is_quoted(text: &str, value: &str) -> bool {
+	text.strip_prefix('"').and_then(|x| x.strip_suffix('"')) == Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::util;
+
+	#[test]
+	fn validate_parsed_value() {
+		let content = r#"
+		{
+			"foo": "",
+			"bar": { "a": [1, 2.5, -3], "b": [] },
+			"baz": [null, false, {}]
+		}
+		"#;
+
+		let value = util::from_str(content).unwrap();
+		assert_eq!(value.validate_spans(content), Ok(()));
+	}
+
+	#[test]
+	fn validate_bad_spans() {
+		let content = r#"["foo", 42]"#;
+
+		let value = Value::new_array(Span::new(0, 11), vec![Value::new_string(Span::new(1, 4), "foo")]);
+		assert_eq!(value.validate_spans(content), Err(SpanError::Mismatch(Span::new(1, 4))));
+
+		let value = Value::new_array(Span::new(0, 11), vec![Value::new_number(Span::new(9, 8), "42")]);
+		assert_eq!(value.validate_spans(content), Err(SpanError::Inverted(Span::new(9, 8))));
+
+		let value = Value::new_array(Span::new(0, 12), vec![]);
+		assert_eq!(value.validate_spans(content), Err(SpanError::OutOfBound(Span::new(0, 12))));
+
+		let value = Value::new_array(Span::new(0, 6), vec![Value::new_number(Span::new(8, 10), "42")]);
+		assert_eq!(
+			value.validate_spans(content),
+			Err(SpanError::NotNested(Span::new(8, 10), Span::new(0, 6)))
+		);
+	}
+}
@@ -0,0 +1,397 @@
+use super::mutate::source_order;
+use super::{Array, Bool, Item, Literal, Null, Number, Object, Value};
+use crate::patch::JsonPointer;
+use crate::util::unescape;
+use std::collections::VecDeque;
+
+/// Walk through a tree by reference, every hook receive the path of the node and the node itself which carry its span
+///
+/// Hooks for objects and arrays call [walk_object()](fn.walk_object.html) and [walk_array()](fn.walk_array.html) by default,
+/// call them yourself when overriding those hooks to keep going deeper. Object members are visited in source order.
+///
+/// ```
+/// # use json_peek::util;
+/// # use json_peek::patch::JsonPointer;
+/// # use json_peek::value::{Literal, Visitor};
+/// struct Strings(Vec<String>);
+///
+/// impl Visitor for Strings {
+///     fn visit_string(&mut self, path: &JsonPointer, literal: &Literal) {
+///         self.0.push(format!("{} {} at {}", path, literal.value, literal.span));
+///     }
+/// }
+///
+/// let value = util::from_str(r#"{ "a": ["x", 1], "b": "y" }"#).unwrap();
+/// let mut strings = Strings(Vec::new());
+/// strings.visit_value(&JsonPointer::root(), &value);
+///
+/// assert_eq!(strings.0, ["/a/0 x at 8..11", "/b y at 22..25"]);
+/// ```
+pub trait Visitor {
+	fn visit_value(&mut self, path: &JsonPointer, value: &Value) {
+		walk_value(self, path, value)
+	}
+
+	fn visit_object(&mut self, path: &JsonPointer, object: &Object) {
+		walk_object(self, path, object)
+	}
+
+	fn visit_array(&mut self, path: &JsonPointer, array: &Array) {
+		walk_array(self, path, array)
+	}
+
+	fn visit_string(&mut self, _path: &JsonPointer, _literal: &Literal) {}
+
+	fn visit_number(&mut self, _path: &JsonPointer, _number: &Number) {}
+
+	fn visit_bool(&mut self, _path: &JsonPointer, _bool: &Bool) {}
+
+	fn visit_null(&mut self, _path: &JsonPointer, _null: &Null) {}
+}
+
+/// Call the [Visitor](trait.Visitor.html) hook that match the kind of `value`
+pub fn walk_value<V: Visitor + ?Sized>(visitor: &mut V, path: &JsonPointer, value: &Value) {
+	match value {
+		Value::Object(item) => visitor.visit_object(path, item),
+		Value::Array(item) => visitor.visit_array(path, item),
+		Value::Literal(item) => visitor.visit_string(path, item),
+		Value::Number(item) => visitor.visit_number(path, item),
+		Value::Bool(item) => visitor.visit_bool(path, item),
+		Value::Null(item) => visitor.visit_null(path, item),
+	}
+}
+
+pub fn walk_object<V: Visitor + ?Sized>(visitor: &mut V, path: &JsonPointer, object: &Object) {
+	for (key, value) in object.iter() {
+		visitor.visit_value(&path.join(unescape(&key.value)), value);
+	}
+}
+
+pub fn walk_array<V: Visitor + ?Sized>(visitor: &mut V, path: &JsonPointer, array: &Array) {
+	for (index, value) in array.value.iter().enumerate() {
+		visitor.visit_value(&path.join(index.to_string()), value);
+	}
+}
+
+/// Same as [Visitor](trait.Visitor.html) but every node can be modified in place
+///
+/// Strings, numbers, booleans and nulls whose value is changed by a hook are marked as [derived](enum.Origin.html#variant.Derived),
+/// so are the objects and arrays that contain a changed or replaced node.
+pub trait VisitorMut {
+	fn visit_value_mut(&mut self, path: &JsonPointer, value: &mut Value) {
+		walk_value_mut(self, path, value)
+	}
+
+	fn visit_object_mut(&mut self, path: &JsonPointer, object: &mut Object) {
+		walk_object_mut(self, path, object)
+	}
+
+	fn visit_array_mut(&mut self, path: &JsonPointer, array: &mut Array) {
+		walk_array_mut(self, path, array)
+	}
+
+	fn visit_string_mut(&mut self, _path: &JsonPointer, _literal: &mut Literal) {}
+
+	fn visit_number_mut(&mut self, _path: &JsonPointer, _number: &mut Number) {}
+
+	fn visit_bool_mut(&mut self, _path: &JsonPointer, _bool: &mut Bool) {}
+
+	fn visit_null_mut(&mut self, _path: &JsonPointer, _null: &mut Null) {}
+}
+
+pub fn walk_value_mut<V: VisitorMut + ?Sized>(visitor: &mut V, path: &JsonPointer, value: &mut Value) {
+	match value {
+		Value::Object(item) => visitor.visit_object_mut(path, item),
+		Value::Array(item) => visitor.visit_array_mut(path, item),
+		Value::Literal(item) => walk_leaf_mut(item, |item| visitor.visit_string_mut(path, item)),
+		Value::Number(item) => walk_leaf_mut(item, |item| visitor.visit_number_mut(path, item)),
+		Value::Bool(item) => walk_leaf_mut(item, |item| visitor.visit_bool_mut(path, item)),
+		Value::Null(item) => walk_leaf_mut(item, |item| visitor.visit_null_mut(path, item)),
+	}
+}
+
+/// Mark `item` as derived if `visit` changed its value
+fn walk_leaf_mut<T: Clone + PartialEq>(item: &mut Item<T>, visit: impl FnOnce(&mut Item<T>)) {
+	let before = item.value.clone();
+	visit(item);
+	if item.value != before {
+		item.mark_derived();
+	}
+}
+
+/// Visit `value` and tell if it was changed or replaced
+fn walk_child_mut<V: VisitorMut + ?Sized>(visitor: &mut V, path: &JsonPointer, value: &mut Value) -> bool {
+	let before = (value.origin(), value.span());
+	visitor.visit_value_mut(path, value);
+	(value.origin(), value.span()) != before
+}
+
+pub fn walk_object_mut<V: VisitorMut + ?Sized>(visitor: &mut V, path: &JsonPointer, object: &mut Object) {
+	let mut is_changed = false;
+	for (key, value) in object.members_mut() {
+		is_changed |= walk_child_mut(visitor, &path.join(unescape(&key.value)), value);
+	}
+	if is_changed {
+		object.mark_derived();
+	}
+}
+
+pub fn walk_array_mut<V: VisitorMut + ?Sized>(visitor: &mut V, path: &JsonPointer, array: &mut Array) {
+	let mut is_changed = false;
+	for (index, value) in array.value.iter_mut().enumerate() {
+		is_changed |= walk_child_mut(visitor, &path.join(index.to_string()), value);
+	}
+	if is_changed {
+		array.mark_derived();
+	}
+}
+
+/// Build a new tree out of an existing one, each hook can return a value of a different kind
+///
+/// ```
+/// # use json_peek::util;
+/// # use json_peek::patch::JsonPointer;
+/// # use json_peek::value::{Fold, Number, Value};
+/// struct Double;
+///
+/// impl Fold for Double {
+///     fn fold_number(&mut self, _path: &JsonPointer, number: Number) -> Value {
+///         let value: f64 = number.value.parse().unwrap();
+///         Value::from(value * 2.0)
+///     }
+/// }
+///
+/// let value = util::from_str(r#"{ "a": [1, 2.5], "b": "3" }"#).unwrap();
+/// let value = Double.fold_value(&JsonPointer::root(), value);
+///
+/// assert_eq!(value, util::from_str(r#"{ "a": [2, 5], "b": "3" }"#).unwrap());
+/// ```
+pub trait Fold {
+	fn fold_value(&mut self, path: &JsonPointer, value: Value) -> Value {
+		fold_value(self, path, value)
+	}
+
+	fn fold_object(&mut self, path: &JsonPointer, object: Object) -> Value {
+		fold_object(self, path, object)
+	}
+
+	fn fold_array(&mut self, path: &JsonPointer, array: Array) -> Value {
+		fold_array(self, path, array)
+	}
+
+	fn fold_string(&mut self, _path: &JsonPointer, literal: Literal) -> Value {
+		Value::Literal(literal)
+	}
+
+	fn fold_number(&mut self, _path: &JsonPointer, number: Number) -> Value {
+		Value::Number(number)
+	}
+
+	fn fold_bool(&mut self, _path: &JsonPointer, bool: Bool) -> Value {
+		Value::Bool(bool)
+	}
+
+	fn fold_null(&mut self, _path: &JsonPointer, null: Null) -> Value {
+		Value::Null(null)
+	}
+}
+
+pub fn fold_value<F: Fold + ?Sized>(folder: &mut F, path: &JsonPointer, value: Value) -> Value {
+	match value {
+		Value::Object(item) => folder.fold_object(path, item),
+		Value::Array(item) => folder.fold_array(path, item),
+		Value::Literal(item) => folder.fold_string(path, item),
+		Value::Number(item) => folder.fold_number(path, item),
+		Value::Bool(item) => folder.fold_bool(path, item),
+		Value::Null(item) => folder.fold_null(path, item),
+	}
+}
+
+/// Fold every member in source order while keeping the keys and the object's span
+pub fn fold_object<F: Fold + ?Sized>(folder: &mut F, path: &JsonPointer, mut object: Object) -> Value {
+	let mut members: Vec<_> = object.value.drain().collect();
+	members.sort_by(|(a, _), (b, _)| source_order(a).cmp(&source_order(b)));
+	object.value = members
+		.into_iter()
+		.map(|(key, value)| {
+			let value = folder.fold_value(&path.join(unescape(&key.value)), value);
+			(key, value)
+		})
+		.collect();
+	Value::Object(object)
+}
+
+/// Fold every element while keeping the array's span
+pub fn fold_array<F: Fold + ?Sized>(folder: &mut F, path: &JsonPointer, mut array: Array) -> Value {
+	array.value = array
+		.value
+		.into_iter()
+		.enumerate()
+		.map(|(index, value)| folder.fold_value(&path.join(index.to_string()), value))
+		.collect();
+	Value::Array(array)
+}
+
+impl Value {
+	/// Iterate through every node along with its path, parents come before their children and siblings are in source order
+	///
+	/// ```
+	/// # use json_peek::util;
+	/// let value = util::from_str(r#"{ "a": [1], "b": 2 }"#).unwrap();
+	/// let paths: Vec<_> = value.depth_first().map(|(path, _)| path.to_string()).collect();
+	///
+	/// assert_eq!(paths, ["", "/a", "/a/0", "/b"]);
+	/// ```
+	pub fn depth_first(&self) -> DepthFirst<'_> {
+		DepthFirst {
+			stack: vec![(JsonPointer::root(), self)],
+		}
+	}
+
+	/// Iterate through every node along with its path, one level of the tree at a time
+	///
+	/// ```
+	/// # use json_peek::util;
+	/// let value = util::from_str(r#"{ "a": [1], "b": 2 }"#).unwrap();
+	/// let paths: Vec<_> = value.breadth_first().map(|(path, _)| path.to_string()).collect();
+	///
+	/// assert_eq!(paths, ["", "/a", "/b", "/a/0"]);
+	/// ```
+	pub fn breadth_first(&self) -> BreadthFirst<'_> {
+		let mut queue = VecDeque::new();
+		queue.push_back((JsonPointer::root(), self));
+		BreadthFirst { queue }
+	}
+}
+
+/// Children of `value` in source order along with their paths
+fn children<'a>(path: &JsonPointer, value: &'a Value) -> Vec<(JsonPointer, &'a Value)> {
+	match value {
+		Value::Object(object) => object.iter().map(|(key, value)| (path.join(unescape(&key.value)), value)).collect(),
+		Value::Array(array) => array
+			.value
+			.iter()
+			.enumerate()
+			.map(|(index, value)| (path.join(index.to_string()), value))
+			.collect(),
+		_ => Vec::new(),
+	}
+}
+
+/// See [Value::depth_first()](enum.Value.html#method.depth_first)
+#[derive(Debug, Clone)]
+pub struct DepthFirst<'a> {
+	stack: Vec<(JsonPointer, &'a Value)>,
+}
+
+impl<'a> Iterator for DepthFirst<'a> {
+	type Item = (JsonPointer, &'a Value);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let (path, value) = self.stack.pop()?;
+		// Pushed in reverse so the first child is on top
+		self.stack.extend(children(&path, value).into_iter().rev());
+		Some((path, value))
+	}
+}
+
+/// See [Value::breadth_first()](enum.Value.html#method.breadth_first)
+#[derive(Debug, Clone)]
+pub struct BreadthFirst<'a> {
+	queue: VecDeque<(JsonPointer, &'a Value)>,
+}
+
+impl<'a> Iterator for BreadthFirst<'a> {
+	type Item = (JsonPointer, &'a Value);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let (path, value) = self.queue.pop_front()?;
+		self.queue.extend(children(&path, value));
+		Some((path, value))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::util::{self, Span};
+	use crate::value::Origin;
+
+	/// Upper case every string and record where numbers are
+	struct Shout(Vec<(String, Span)>);
+
+	impl VisitorMut for Shout {
+		fn visit_string_mut(&mut self, _path: &JsonPointer, literal: &mut Literal) {
+			literal.value = literal.value.to_uppercase();
+		}
+
+		fn visit_number_mut(&mut self, path: &JsonPointer, number: &mut Number) {
+			self.0.push((path.to_string(), number.span));
+		}
+	}
+
+	#[test]
+	fn visit_mut() {
+		let mut value = util::from_str(r#"[{ "a": "x" }, 1, ["y", 2]]"#).unwrap();
+		let mut shout = Shout(Vec::new());
+		shout.visit_value_mut(&JsonPointer::root(), &mut value);
+
+		assert_eq!(value.to_string(), r#"[{"a":"X"},1,["Y",2]]"#);
+		assert_eq!(shout.0, [("/1".to_owned(), Span::new(15, 16)), ("/2/1".to_owned(), Span::new(24, 25))]);
+
+		let origins: Vec<_> = value.depth_first().map(|(path, value)| (path.to_string(), value.origin())).collect();
+		assert_eq!(origins, [
+			("".to_owned(), Origin::Derived),
+			("/0".to_owned(), Origin::Derived),
+			("/0/a".to_owned(), Origin::Derived),
+			("/1".to_owned(), Origin::Source),
+			("/2".to_owned(), Origin::Derived),
+			("/2/0".to_owned(), Origin::Derived),
+			("/2/1".to_owned(), Origin::Source),
+		]);
+
+		let mut unchanged = util::from_str("[{}, 1]").unwrap();
+		Shout(Vec::new()).visit_value_mut(&JsonPointer::root(), &mut unchanged);
+		assert_eq!(unchanged.origin(), Origin::Source);
+	}
+
+	/// Replace every object with how many members it has
+	struct Count;
+
+	impl Fold for Count {
+		fn fold_object(&mut self, _path: &JsonPointer, object: Object) -> Value {
+			Value::from(object.len())
+		}
+	}
+
+	#[test]
+	fn fold_changes_kind() {
+		let value = util::from_str(r#"[{ "a": 1, "b": {} }, [{}]]"#).unwrap();
+		assert_eq!(Count.fold_value(&JsonPointer::root(), value).to_string(), "[2,[0]]");
+	}
+
+	/// Record the path of every number
+	struct Order(Vec<String>);
+
+	impl Fold for Order {
+		fn fold_number(&mut self, path: &JsonPointer, number: Number) -> Value {
+			self.0.push(path.to_string());
+			Value::Number(number)
+		}
+	}
+
+	#[test]
+	fn fold_in_source_order() {
+		let value = util::from_str(r#"{ "z": 1, "a": 2, "m": { "y": 3, "b": 4 }, "c": 5, "x": 6, "d": 7 }"#).unwrap();
+		let mut order = Order(Vec::new());
+		order.fold_value(&JsonPointer::root(), value);
+		assert_eq!(order.0, ["/z", "/a", "/m/y", "/m/b", "/c", "/x", "/d"]);
+	}
+
+	#[test]
+	fn iterators_visit_every_node() {
+		let value = util::from_str(r#"[[[1]], {"a": [2]}, 3]"#).unwrap();
+		assert_eq!(value.depth_first().count(), 8);
+		assert_eq!(value.breadth_first().last().map(|(path, _)| path.to_string()), Some("/1/a/0".to_owned()));
+	}
+}